@@ -1,3 +1,14 @@
+use crossterm::event::KeyEvent;
+
+use crate::{
+    api::{session::SessionStatus, FetchResult},
+    export::Format,
+    model::{
+        CheckIn, Feed, Member, MemberProfile, Node, Notification, Reply, Tab, Topic, TopicDetail,
+    },
+    theme::Preset,
+};
+
 #[derive(PartialEq, Debug)]
 pub enum Action {
     PreviousRow,
@@ -6,11 +17,107 @@ pub enum Action {
     Bottom,
     Enter,
     Reload,
+    PreviousTab,
+    NextTab,
+    SelectTab(Tab),
     FetchTopics,
+    TopicsLoaded(Feed, FetchResult<Vec<Topic>>),
+    MoreTopicsLoaded(Feed, u32, FetchResult<Vec<Topic>>),
     FetchTopicDetail(String),
-    GoHome,
+    TopicDetailLoaded(String, FetchResult<TopicDetail>),
+    FetchReplyPage(u32),
+    FetchAllReplyPages,
+    RepliesLoaded(String, u32, FetchResult<Vec<Reply>>),
+    GoNodes,
+    FetchNodes,
+    NodesLoaded(FetchResult<Vec<Node>>),
+    OpenNode {
+        name: String,
+        title: String,
+        header: String,
+    },
+    /// Looks a node up by name before opening it.
+    FetchNode(String),
+    NodeLoaded(String, FetchResult<Node>),
+    OpenTopic(String),
+    GoNewTopic,
+    NextField,
+    PreviousField,
+    PickNode,
+    ToggleSyntax,
+    TogglePreview,
+    SubmitTopic,
+    TopicPosted(FetchResult<String>),
+    FilterInput(char),
+    FilterBackspace,
+    StartFilter,
+    EndFilter,
+    GoNotifications,
+    FetchNotifications,
+    NotificationsLoaded(u32, FetchResult<Vec<Notification>>),
+    CheckUnread,
+    UnreadLoaded(FetchResult<u32>),
+    CheckIn,
+    CheckedIn(FetchResult<CheckIn>),
+    TopicRead {
+        url: String,
+        replies: u32,
+        floor: u32,
+    },
+    GoBookmarks,
+    AddBookmark(String),
+    SaveBookmark(String, TopicDetail),
+    SnapshotLoaded(String, FetchResult<TopicDetail>),
+    BookmarkSaved(Result<String, String>),
+    RefreshBookmark,
+    DeleteBookmark,
+    EditTags,
+    SaveTags,
+    OpenSnapshot(String, TopicDetail),
+    GoHistory,
+    TopicVisited {
+        url: String,
+        title: String,
+        scroll: u16,
+    },
+    RestoreSession,
+    ResumeTopic {
+        url: String,
+        scroll: u16,
+    },
+    DeleteHistoryEntry,
+    ClearHistory,
+    OpenMember(String),
+    FetchMember(String),
+    MemberLoaded(String, FetchResult<MemberProfile>),
+    GoBack,
+    GoForward,
+    DismissError,
+    TokenChecked(FetchResult<Member>),
+    SessionChecked(FetchResult<SessionStatus>),
+    Compose,
+    ComposerInput(KeyEvent),
+    CloseComposer,
+    SubmitReply,
+    ReplyPosted(String, FetchResult<()>),
+    OpenEditor(String),
+    EditorClosed(Result<String, String>),
+    Thank,
+    ToggleFavorite,
+    IgnoreTopic,
+    Confirm,
+    CancelConfirm,
+    ThankSent(String, Option<u64>, FetchResult<()>),
+    FavoriteSet(String, bool, FetchResult<()>),
+    TopicIgnored(String, FetchResult<()>),
     LineUp(u16),
     LineDown(u16),
     OpenBrowser(String),
+    SetTheme(Preset),
+    ExportTopic {
+        format: Format,
+        path: Option<String>,
+    },
+    Tick,
     Quit,
 }
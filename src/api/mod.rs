@@ -0,0 +1,1471 @@
+use std::{fmt, sync::Arc, time::Duration};
+
+use atom_syndication::Feed as AtomFeed;
+use chrono::{DateTime, Utc};
+use reqwest::{cookie::Jar, header::REFERER, Url};
+use scraper::{Html, Selector};
+use serde::Deserialize;
+
+use crate::i18n::msg;
+use crate::model::{
+    CheckIn, Feed, Member, MemberProfile, MemberReply, Node, Notification, NotificationKind, Reply,
+    Syntax, Tab, Topic, TopicDetail,
+};
+use crate::time::time_formatting::format_relative_time;
+use session::{parse_username, Session, SessionStatus};
+use v1::{V1Client, V2EX_API_URL};
+use v2::{RateLimit, V2Client};
+
+pub mod session;
+#[cfg(test)]
+mod stub;
+pub mod v1;
+pub mod v2;
+
+pub const V2EX_URL: &str = "https://www.v2ex.com";
+/// Replies per page of a topic on the site.
+pub const REPLIES_PER_PAGE: u32 = 100;
+/// Sent with every request; V2EX hands less to clients it does not recognise.
+pub const DEFAULT_USER_AGENT: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36";
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A failed request, kept as plain data so it can travel through `Action`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FetchError {
+    pub url: String,
+    pub status: Option<u16>,
+    pub message: String,
+}
+
+impl FetchError {
+    pub fn new(url: &str, message: impl fmt::Display) -> Self {
+        Self {
+            url: url.to_string(),
+            status: None,
+            message: message.to_string(),
+        }
+    }
+
+    fn from_reqwest(url: &str, err: reqwest::Error) -> Self {
+        Self {
+            url: err.url().map_or(url.to_string(), |u| u.to_string()),
+            status: err.status().map(|s| s.as_u16()),
+            message: err.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.status {
+            Some(status) => write!(f, "{} ({}): {}", self.url, status, self.message),
+            None => write!(f, "{}: {}", self.url, self.message),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+pub type FetchResult<T> = Result<T, FetchError>;
+
+/// Which backend `Crawler` asks first when both can answer; the other is the fallback.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Source {
+    #[default]
+    Api,
+    Html,
+}
+
+#[derive(Clone)]
+pub struct Crawler {
+    client: reqwest::blocking::Client,
+    // The site itself, or a mirror of it.
+    base_url: String,
+    jar: Arc<Jar>,
+    session: Option<Session>,
+    v1: V1Client,
+    // Only present when a personal access token is configured.
+    v2: Option<V2Client>,
+    source: Source,
+}
+
+impl Crawler {
+    #[cfg(test)]
+    pub fn new() -> Self {
+        Self::with_client(DEFAULT_USER_AGENT, DEFAULT_TIMEOUT).unwrap()
+    }
+
+    /// Fails when the client cannot be built, e.g. for a user agent that is
+    /// not a valid header value.
+    pub fn with_client(user_agent: &str, timeout: Duration) -> reqwest::Result<Self> {
+        let jar = Arc::new(Jar::default());
+        let client = reqwest::blocking::Client::builder()
+            .user_agent(user_agent)
+            .timeout(timeout)
+            .cookie_provider(jar.clone())
+            .build()?;
+        Ok(Self {
+            v1: V1Client::new(client.clone(), V2EX_API_URL),
+            v2: None,
+            client,
+            base_url: V2EX_URL.to_string(),
+            jar,
+            session: None,
+            source: Source::Api,
+        })
+    }
+
+    /// Sets the login cookie, which belongs to the site at the base url; set
+    /// that first.
+    pub fn with_session(mut self, session: Session) -> Self {
+        session.apply(&self.jar, &self.site_url());
+        self.session = Some(session);
+        self
+    }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self.v1 = V1Client::new(self.client.clone(), format!("{}/api", self.base_url));
+        self
+    }
+
+    fn logged_in(&self) -> bool {
+        self.session.as_ref().is_some_and(Session::has_cookie)
+    }
+
+    fn site_url(&self) -> Url {
+        Url::parse(&self.base_url).unwrap_or_else(|_| Url::parse(V2EX_URL).unwrap())
+    }
+
+    /// Checks whether V2EX still accepts the session cookie and saves it if so.
+    pub fn check_session(&self) -> FetchResult<SessionStatus> {
+        let Some(session) = self.session.as_ref().filter(|s| s.has_cookie()) else {
+            return Ok(SessionStatus::Anonymous);
+        };
+        let document = self.fetch_document(&self.base_url)?;
+        match parse_username(&document) {
+            Some(username) => {
+                session.save(&self.jar, &self.site_url()).map_err(|e| {
+                    FetchError::new(&self.base_url, (msg().session_save_failed)(&e.to_string()))
+                })?;
+                Ok(SessionStatus::LoggedIn(username))
+            }
+            None => Ok(SessionStatus::Expired),
+        }
+    }
+
+    pub fn with_source(mut self, source: Source) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Sets the API v2 token, which belongs to the site at the base url; set
+    /// that first.
+    pub fn with_token(mut self, token: Option<String>) -> Self {
+        let api_url = format!("{}/api/v2", self.base_url);
+        self.v2 = token.map(|token| V2Client::new(self.client.clone(), api_url, token));
+        self
+    }
+
+    pub fn has_token(&self) -> bool {
+        self.v2.is_some()
+    }
+
+    /// The API v2 quota left, once a v2 request has been made.
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        self.v2.as_ref().and_then(|v2| v2.rate_limit())
+    }
+
+    /// The member the access token belongs to, which also tells whether it still works.
+    pub fn fetch_token_owner(&self) -> FetchResult<Member> {
+        match &self.v2 {
+            Some(v2) => v2.member(),
+            None => Err(FetchError::new(
+                &format!("{}/api/v2", self.base_url),
+                msg().token_missing,
+            )),
+        }
+    }
+
+    /// Tries the preferred source and falls back to the other one, reporting the first error.
+    fn either<T>(
+        &self,
+        api: impl FnOnce() -> FetchResult<T>,
+        html: impl FnOnce() -> FetchResult<T>,
+    ) -> FetchResult<T> {
+        match self.source {
+            Source::Api => api().or_else(|err| html().map_err(|_| err)),
+            Source::Html => html().or_else(|err| api().map_err(|_| err)),
+        }
+    }
+
+    fn get(&self, url: &str) -> FetchResult<reqwest::blocking::Response> {
+        self.client
+            .get(url)
+            .send()
+            .and_then(|resp| resp.error_for_status())
+            .map_err(|e| FetchError::from_reqwest(url, e))
+    }
+
+    pub fn fetch_topics(&self, tab: Tab) -> FetchResult<Vec<Topic>> {
+        let rss = || self.fetch_feed(&format!("{}/feed/tab/{}.xml", self.base_url, tab.name()));
+        match tab {
+            Tab::Hot => self.either(|| self.v1.hot_topics(), rss),
+            Tab::All => self.either(|| self.v1.latest_topics(), rss),
+            _ => rss(),
+        }
+    }
+
+    pub fn fetch_node_topics(&self, node: &str) -> FetchResult<Vec<Topic>> {
+        self.fetch_feed(&format!("{}/feed/{}.xml", self.base_url, node))
+    }
+
+    pub fn fetch_nodes(&self) -> FetchResult<Vec<Node>> {
+        self.v1.nodes()
+    }
+
+    pub fn fetch_node(&self, name: &str) -> FetchResult<Node> {
+        self.v1.node(name)
+    }
+
+    /// Fetches page `page` of the HTML topic listing behind a feed, which reaches
+    /// further back than its RSS window. Feeds without a listing yield nothing.
+    pub fn fetch_topic_list(&self, feed: &Feed, page: u32) -> FetchResult<Vec<Topic>> {
+        let html = || match self.listing_url(feed, page) {
+            Some(url) => Ok(parse_topic_list(
+                &self.fetch_document(&url)?,
+                &self.base_url,
+            )),
+            None => Ok(vec![]),
+        };
+        match (feed, &self.v2) {
+            (Feed::Node { name, .. }, Some(v2)) => self.either(|| v2.node_topics(name, page), html),
+            _ => html(),
+        }
+    }
+
+    /// The paginated HTML listing for a feed, if V2EX has one.
+    fn listing_url(&self, feed: &Feed, page: u32) -> Option<String> {
+        match feed {
+            Feed::Tab(Tab::All) => Some(format!("{}/recent?p={}", self.base_url, page)),
+            Feed::Tab(_) => None,
+            Feed::Node { name, .. } => Some(format!("{}/go/{}?p={}", self.base_url, name, page)),
+        }
+    }
+
+    fn fetch_feed(&self, url: &str) -> FetchResult<Vec<Topic>> {
+        let content = self
+            .get(url)?
+            .bytes()
+            .map_err(|e| FetchError::from_reqwest(url, e))?;
+
+        let feed = AtomFeed::read_from(content.as_ref()).map_err(|e| FetchError::new(url, e))?;
+
+        let topics: Vec<Topic> = feed
+            .entries()
+            .iter()
+            .filter_map(|entry| {
+                let link = entry.links().first().map(|c| c.href())?;
+
+                // Extract comment count from content if available
+                let comment = link
+                    .split("#reply")
+                    .nth(1)
+                    .and_then(|s| s.trim().parse::<String>().ok())
+                    .unwrap_or_else(|| "0".to_string());
+
+                // Format the time
+                let updated = format_relative_time(entry.updated().with_timezone(&Utc));
+
+                Some(Topic::new(
+                    entry.title().to_string(),
+                    entry
+                        .authors()
+                        .first()
+                        .map_or("".to_string(), |a| a.name().to_string()),
+                    comment,
+                    String::new(),
+                    updated,
+                    link.to_string(),
+                ))
+            })
+            .collect();
+
+        Ok(topics)
+    }
+
+    fn fetch_document(&self, url: &str) -> FetchResult<Html> {
+        let resp = self
+            .get(url)?
+            .text()
+            .map_err(|e| FetchError::from_reqwest(url, e))?;
+        Ok(Html::parse_document(&resp))
+    }
+
+    /// Fetches the topic with its replies. The API returns them all at once, while
+    /// the HTML page only has the first page; see `fetch_topic_replies` for the rest.
+    /// Only the page knows what the member favorited and thanked, so it comes
+    /// first whenever there is a session.
+    pub fn fetch_topic_detail(&self, url: &str) -> FetchResult<TopicDetail> {
+        match topic_id(url) {
+            Some(id) if self.logged_in() => self
+                .scrape_topic_detail(url)
+                .or_else(|err| self.v1.topic(id).map_err(|_| err)),
+            Some(id) => self.either(|| self.v1.topic(id), || self.scrape_topic_detail(url)),
+            None => self.scrape_topic_detail(url),
+        }
+    }
+
+    fn scrape_topic_detail(&self, url: &str) -> FetchResult<TopicDetail> {
+        let document = self.fetch_document(url)?;
+
+        // Selectors
+        let title_selector = Selector::parse("h1").unwrap();
+        let author_selector = Selector::parse(".header small a").unwrap();
+        let time_selector = Selector::parse(".header small span").unwrap();
+        let content_selector = Selector::parse(".topic_content").unwrap();
+
+        let title = document
+            .select(&title_selector)
+            .next()
+            .map(|el| el.text().collect::<String>())
+            .unwrap_or_default();
+
+        let author = document
+            .select(&author_selector)
+            .next()
+            .map(|el| el.text().collect::<String>())
+            .unwrap_or_default();
+
+        let time = document
+            .select(&time_selector)
+            .next()
+            .and_then(|el| el.value().attr("title"))
+            .unwrap_or_default()
+            .to_string();
+
+        let content = document
+            .select(&content_selector)
+            .next()
+            .map(|el| el.text().collect::<String>())
+            .unwrap_or_default();
+
+        let mut replies = parse_replies(&document);
+        count_replies(&mut replies);
+        let (favorites, favorited, thanked) = parse_topic_marks(&document);
+        let reply_count = parse_reply_count(&document)
+            .or_else(|| replies.iter().map(Reply::floor).max())
+            .unwrap_or_default();
+
+        Ok(TopicDetail {
+            title,
+            content,
+            author,
+            comment: reply_count.to_string(),
+            updated: time,
+            link: url.to_string(),
+            replies,
+            page_count: parse_page_count(&document),
+            favorites,
+            favorited,
+            thanked,
+        })
+    }
+
+    /// Fetches the replies on page `page` (1-based) of a topic, as the site
+    /// pages them. As with the topic itself, the page comes first when there
+    /// is a session.
+    pub fn fetch_topic_replies(&self, url: &str, page: u32) -> FetchResult<Vec<Reply>> {
+        let html = || Ok(parse_replies(&self.fetch_document(&page_url(url, page))?));
+        match (topic_id(url), &self.v2) {
+            (Some(id), Some(v2)) if self.logged_in() => {
+                html().or_else(|err| site_page_replies(v2, id, page).map_err(|_| err))
+            }
+            (Some(id), Some(v2)) => self.either(|| site_page_replies(v2, id, page), html),
+            _ => html(),
+        }
+    }
+
+    /// The topic with every page of its replies, for reading offline.
+    pub fn fetch_topic_snapshot(&self, url: &str) -> FetchResult<TopicDetail> {
+        let mut detail = self.fetch_topic_detail(url)?;
+        for page in 2..=detail.page_count {
+            detail.replies.extend(self.fetch_topic_replies(url, page)?);
+        }
+        merge_replies(&mut detail.replies);
+        Ok(detail)
+    }
+
+    /// A member's profile with their recent topics and replies, which only the
+    /// HTML page lists. The profile itself also comes from the API when it answers.
+    pub fn fetch_member(&self, username: &str) -> FetchResult<MemberProfile> {
+        let document = self.fetch_document(&format!("{}/member/{}", self.base_url, username))?;
+        let member = self.either(
+            || self.v1.member(username),
+            || Ok(parse_member(&document, username)),
+        )?;
+        Ok(MemberProfile {
+            member,
+            topics: parse_topic_list(&document, &self.base_url),
+            replies: parse_member_replies(&document, &self.base_url),
+        })
+    }
+
+    /// Fetches page `page` (1-based) of the member's notifications. Reading them
+    /// on the site also clears the unread count, the API leaves it alone.
+    pub fn fetch_notifications(&self, page: u32) -> FetchResult<Vec<Notification>> {
+        let html = || {
+            let url = format!("{}/notifications?p={}", self.base_url, page);
+            Ok(parse_notifications(
+                &self.fetch_document(&url)?,
+                &self.base_url,
+            ))
+        };
+        match &self.v2 {
+            Some(v2) => self.either(|| v2.notifications(page), html),
+            None => html(),
+        }
+    }
+
+    /// The unread notification count from the sidebar, which needs the session cookie.
+    pub fn fetch_unread_count(&self) -> FetchResult<u32> {
+        parse_unread_count(&self.fetch_document(&self.base_url)?)
+            .ok_or_else(|| FetchError::new(&self.base_url, msg().unread_missing))
+    }
+
+    /// Replies to a topic as the logged-in member, using the one-time `once`
+    /// token from the reply form on the topic page.
+    pub fn post_reply(&self, url: &str, content: &str) -> FetchResult<()> {
+        let url = url.split(['#', '?']).next().unwrap_or(url);
+        let once = self.fetch_once(url)?;
+        self.post_form(url, &[("content", content), ("once", &once)])?;
+        Ok(())
+    }
+
+    /// Thanks a reply on the topic at `url`, which costs the member copper coins.
+    pub fn thank_reply(&self, url: &str, reply_id: u64) -> FetchResult<()> {
+        self.thank(url, &format!("/thank/reply/{}", reply_id))
+    }
+
+    pub fn thank_topic(&self, url: &str) -> FetchResult<()> {
+        let id = topic_id(url).ok_or_else(|| FetchError::new(url, msg().not_a_topic))?;
+        self.thank(url, &format!("/thank/topic/{}", id))
+    }
+
+    /// Thanks are posted from the page's script and answered with JSON.
+    fn thank(&self, url: &str, path: &str) -> FetchResult<()> {
+        #[derive(Deserialize)]
+        struct ThankResult {
+            success: bool,
+            #[serde(default)]
+            message: String,
+        }
+
+        let once = self.fetch_once(url)?;
+        let action = format!("{}{}?once={}", self.base_url, path, once);
+        let result: ThankResult = self
+            .client
+            .post(&action)
+            .header(REFERER, url)
+            .send()
+            .and_then(|resp| resp.error_for_status())
+            .and_then(|resp| resp.json())
+            .map_err(|e| FetchError::from_reqwest(&action, e))?;
+        if result.success {
+            Ok(())
+        } else {
+            Err(FetchError::new(&action, result.message))
+        }
+    }
+
+    /// Adds the topic to or removes it from the member's favorites.
+    pub fn favorite_topic(&self, url: &str, favorite: bool) -> FetchResult<()> {
+        self.topic_link(url, if favorite { "favorite" } else { "unfavorite" })
+    }
+
+    /// Hides the topic from the member's listings.
+    pub fn ignore_topic(&self, url: &str) -> FetchResult<()> {
+        self.topic_link(url, "ignore")
+    }
+
+    /// Follows one of the `/{verb}/topic/{id}?once=…` links on a topic page,
+    /// which redirect back to the topic, or to the sign-in page when logged out.
+    fn topic_link(&self, url: &str, verb: &str) -> FetchResult<()> {
+        let id = topic_id(url).ok_or_else(|| FetchError::new(url, msg().not_a_topic))?;
+        let once = self.fetch_once(url)?;
+        let action = format!("{}/{}/topic/{}?once={}", self.base_url, verb, id, once);
+        self.follow(&action, url).map(|_| ())
+    }
+
+    /// Follows a link the way a click from `referer` would, failing when it ends
+    /// on the sign-in page.
+    fn follow(&self, url: &str, referer: &str) -> FetchResult<Html> {
+        let resp = self
+            .client
+            .get(url)
+            .header(REFERER, referer)
+            .send()
+            .and_then(|resp| resp.error_for_status())
+            .map_err(|e| FetchError::from_reqwest(url, e))?;
+        if resp.url().path().starts_with("/signin") {
+            return Err(FetchError::new(url, msg().session_lost));
+        }
+        let text = resp.text().map_err(|e| FetchError::from_reqwest(url, e))?;
+        Ok(Html::parse_document(&text))
+    }
+
+    /// Claims the daily login reward at `/mission/daily`, unless it was claimed
+    /// already today.
+    pub fn check_in(&self) -> FetchResult<CheckIn> {
+        let url = format!("{}/mission/daily", self.base_url);
+        let mission = parse_mission(&self.follow(&url, &self.base_url)?);
+        if mission.claimed {
+            return Ok(CheckIn {
+                claimed: false,
+                coins: None,
+                streak: mission.streak,
+            });
+        }
+        let once = mission
+            .once
+            .ok_or_else(|| FetchError::new(&url, msg().redeem_missing))?;
+        let redeem = format!("{}/mission/daily/redeem?once={}", self.base_url, once);
+        let document = self.follow(&redeem, &url)?;
+        if let Some(problem) = parse_problem(&document) {
+            return Err(FetchError::new(&redeem, problem));
+        }
+        let mission = parse_mission(&document);
+        if !mission.claimed {
+            return Err(FetchError::new(&redeem, msg().redeem_failed));
+        }
+        // The amount is only listed on the balance page; not knowing it is no failure.
+        let coins = self
+            .fetch_document(&format!("{}/balance", self.base_url))
+            .ok()
+            .and_then(|document| parse_daily_reward(&document));
+        Ok(CheckIn {
+            claimed: true,
+            coins,
+            streak: mission.streak,
+        })
+    }
+
+    /// Fetches the one-time token from a page, which V2EX only hands to members.
+    fn fetch_once(&self, url: &str) -> FetchResult<String> {
+        let url = url.split('#').next().unwrap_or(url);
+        parse_once(&self.fetch_document(url)?)
+            .ok_or_else(|| FetchError::new(url, msg().once_missing))
+    }
+
+    /// Creates a topic in `node` and returns its url, which V2EX redirects to.
+    pub fn post_topic(
+        &self,
+        node: &str,
+        title: &str,
+        content: &str,
+        syntax: Syntax,
+    ) -> FetchResult<String> {
+        let url = format!("{}/write", self.base_url);
+        let once = self.fetch_once(&format!("{}?node={}", url, node))?;
+        let (location, _) = self.post_form(
+            &url,
+            &[
+                ("title", title),
+                ("content", content),
+                ("node_name", node),
+                ("syntax", syntax.name()),
+                ("once", &once),
+            ],
+        )?;
+        match topic_id(&location) {
+            Some(id) => Ok(format!("{}/t/{}", self.base_url, id)),
+            None => Err(FetchError::new(&url, msg().topic_not_created)),
+        }
+    }
+
+    /// Submits a form the way the browser would, returning the url it ended up on
+    /// after redirects and that page. V2EX answers a rejected form with the form
+    /// page again, listing the problems, rather than an error status.
+    fn post_form(&self, url: &str, form: &[(&str, &str)]) -> FetchResult<(String, Html)> {
+        let resp = self
+            .client
+            .post(url)
+            .header(REFERER, url)
+            .form(form)
+            .send()
+            .and_then(|resp| resp.error_for_status())
+            .map_err(|e| FetchError::from_reqwest(url, e))?;
+        let location = resp.url().to_string();
+        let text = resp.text().map_err(|e| FetchError::from_reqwest(url, e))?;
+        let document = Html::parse_document(&text);
+        match parse_problem(&document) {
+            Some(problem) => Err(FetchError::new(url, problem)),
+            None => Ok((location, document)),
+        }
+    }
+}
+
+/// Strips any fragment or query from a topic url and asks for the given page.
+pub fn page_url(url: &str, page: u32) -> String {
+    let base = url.split(['#', '?']).next().unwrap_or(url);
+    format!("{}?p={}", base, page)
+}
+
+/// The floor a url such as `https://www.v2ex.com/t/1111950#reply45` points at.
+pub fn reply_floor(url: &str) -> Option<u32> {
+    url.split_once("#reply")?.1.parse().ok()
+}
+
+/// Extracts the topic id from a url such as `https://www.v2ex.com/t/1111950#reply45`.
+pub fn topic_id(url: &str) -> Option<u64> {
+    let rest = &url[url.find("/t/")? + 3..];
+    let end = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    rest[..end].parse().ok()
+}
+
+/// Topic rows of an HTML listing; their links point into `base_url`.
+fn parse_topic_list(document: &Html, base_url: &str) -> Vec<Topic> {
+    let cell_selector = Selector::parse(".cell").unwrap();
+    let title_selector = Selector::parse(".item_title a").unwrap();
+    let author_selector = Selector::parse(".topic_info strong a").unwrap();
+    let time_selector = Selector::parse(".topic_info span[title]").unwrap();
+    let count_selector = Selector::parse(".count_livid").unwrap();
+
+    document
+        .select(&cell_selector)
+        .filter_map(|cell| {
+            let title = cell.select(&title_selector).next()?;
+            let href = title.value().attr("href")?;
+
+            let author = cell
+                .select(&author_selector)
+                .next()
+                .map(|el| el.text().collect::<String>())
+                .unwrap_or_default();
+
+            let updated = cell
+                .select(&time_selector)
+                .next()
+                .map(|el| {
+                    el.value()
+                        .attr("title")
+                        .and_then(|t| DateTime::parse_from_str(t, "%Y-%m-%d %H:%M:%S %z").ok())
+                        .map(|t| format_relative_time(t.with_timezone(&Utc)))
+                        .unwrap_or_else(|| el.text().collect())
+                })
+                .unwrap_or_default();
+
+            let comment = cell
+                .select(&count_selector)
+                .next()
+                .map(|el| el.text().collect::<String>())
+                .unwrap_or_else(|| "0".to_string());
+
+            Some(Topic::new(
+                title.text().collect(),
+                author,
+                comment,
+                String::new(),
+                updated,
+                format!("{}{}", base_url, href),
+            ))
+        })
+        .collect()
+}
+
+fn parse_replies(document: &Html) -> Vec<Reply> {
+    let replies_selector = Selector::parse(".cell[id^='r_']").unwrap();
+    let reply_content_selector = Selector::parse(".reply_content").unwrap();
+    let reply_author_selector = Selector::parse("strong a").unwrap();
+    let reply_time_selector = Selector::parse(".ago").unwrap();
+    let reply_number_selector = Selector::parse(".no").unwrap();
+    // The heart next to the time, followed by the number of thanks.
+    let reply_thanks_selector = Selector::parse("span.small.fade").unwrap();
+    let reply_thanked_selector = Selector::parse(".thanked").unwrap();
+
+    let mut replies = Vec::new();
+    for element in document.select(&replies_selector) {
+        let id = element
+            .value()
+            .id()
+            .and_then(|id| id.strip_prefix("r_"))
+            .and_then(|id| id.parse().ok())
+            .unwrap_or_default();
+
+        let content = element
+            .select(&reply_content_selector)
+            .next()
+            .map(|el| el.text().collect::<String>())
+            .unwrap_or_default();
+
+        let author = element
+            .select(&reply_author_selector)
+            .next()
+            .map(|el| el.text().collect::<String>())
+            .unwrap_or_default();
+
+        let time = element
+            .select(&reply_time_selector)
+            .next()
+            .map(|el| el.text().collect::<String>())
+            .unwrap_or_default();
+
+        let number = element
+            .select(&reply_number_selector)
+            .next()
+            .map(|el| el.text().collect::<String>())
+            .unwrap_or_default();
+
+        let reply_to = if content.trim().starts_with('@') {
+            content
+                .split_whitespace()
+                .next()
+                .map(|s| s[1..].to_string())
+                .unwrap_or_default()
+        } else {
+            String::new()
+        };
+
+        let thanks = element
+            .select(&reply_thanks_selector)
+            .find_map(|el| el.text().collect::<String>().trim().parse().ok())
+            .unwrap_or_default();
+        let thanked = element.select(&reply_thanked_selector).next().is_some();
+
+        replies.push(Reply {
+            id,
+            author,
+            time,
+            content,
+            number,
+            reply_to,
+            reply_count: 0,
+            thanks,
+            thanked,
+        });
+    }
+    replies
+}
+
+/// Recalculates how many replies mention each reply's author.
+pub fn count_replies(replies: &mut [Reply]) {
+    for i in 0..replies.len() {
+        let count = replies
+            .iter()
+            .filter(|r| r.reply_to == replies[i].author)
+            .count() as i32;
+        replies[i].reply_count = count;
+    }
+}
+
+/// The replies on page `page` of a topic on the site, gathered from the
+/// smaller API v2 pages it spans.
+fn site_page_replies(v2: &V2Client, topic_id: u64, page: u32) -> FetchResult<Vec<Reply>> {
+    let per_page = REPLIES_PER_PAGE / v2::PAGE_SIZE;
+    let first = (page.max(1) - 1) * per_page + 1;
+    let mut replies = vec![];
+    for api_page in first..first + per_page {
+        let more = v2.topic_replies(topic_id, api_page)?;
+        let last = (more.len() as u32) < v2::PAGE_SIZE;
+        replies.extend(more);
+        if last {
+            break;
+        }
+    }
+    Ok(replies)
+}
+
+/// Puts replies gathered from several pages in floor order, drops the ones
+/// fetched twice and recounts them. Replies match by id, or lacking one by
+/// floor, and the copy with an id stays; those with neither are all kept.
+pub fn merge_replies(replies: &mut Vec<Reply>) {
+    replies.sort_by_key(|reply| (reply.floor(), std::cmp::Reverse(reply.id)));
+    replies.dedup_by(|a, b| {
+        if a.id != 0 && b.id != 0 {
+            a.id == b.id
+        } else {
+            a.floor() > 0 && a.floor() == b.floor()
+        }
+    });
+    count_replies(replies);
+}
+
+/// Reads "123 条回复" from the box above the replies.
+fn parse_reply_count(document: &Html) -> Option<u32> {
+    let gray_selector = Selector::parse(".box .cell .gray").unwrap();
+    document.select(&gray_selector).find_map(|el| {
+        let text = el.text().collect::<String>();
+        let (count, _) = text.split_once("条回复")?;
+        count.split_whitespace().last()?.parse().ok()
+    })
+}
+
+/// Reads the number of reply pages from the pagination control, 1 if there is none.
+fn parse_page_count(document: &Html) -> u32 {
+    let input_selector = Selector::parse("input.page_input").unwrap();
+    let link_selector = Selector::parse("a.page_normal, a.page_current").unwrap();
+
+    if let Some(max) = document
+        .select(&input_selector)
+        .next()
+        .and_then(|el| el.value().attr("max"))
+        .and_then(|max| max.parse().ok())
+    {
+        return max;
+    }
+    document
+        .select(&link_selector)
+        .filter_map(|el| el.text().collect::<String>().trim().parse().ok())
+        .max()
+        .unwrap_or(1)
+}
+
+/// The one-time token V2EX puts in its forms and action links to guard against replays.
+fn parse_once(document: &Html) -> Option<String> {
+    let input_selector = Selector::parse("input[name='once']").unwrap();
+    let link_selector = Selector::parse("a[href*='once=']").unwrap();
+    if let Some(once) = document
+        .select(&input_selector)
+        .next()
+        .and_then(|el| el.value().attr("value"))
+    {
+        return Some(once.to_string());
+    }
+    document.select(&link_selector).find_map(|el| {
+        let href = el.value().attr("href")?;
+        let once = &href[href.find("once=")? + 5..];
+        let end = once
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(once.len());
+        (end > 0).then(|| once[..end].to_string())
+    })
+}
+
+/// How many members favorited the topic, and whether the logged-in member
+/// favorited or thanked it.
+fn parse_topic_marks(document: &Html) -> (u32, bool, bool) {
+    let stats_selector = Selector::parse(".topic_stats").unwrap();
+    let unfavorite_selector = Selector::parse("a[href^='/unfavorite/topic/']").unwrap();
+    let thanked_selector = Selector::parse("#topic_thank .topic_thanked").unwrap();
+
+    let favorites = document
+        .select(&stats_selector)
+        .next()
+        .map(|el| el.text().collect::<String>())
+        .and_then(|text| {
+            let before = &text[..text.find("人收藏")?];
+            before.split_whitespace().last()?.parse().ok()
+        })
+        .unwrap_or_default();
+    let favorited = document.select(&unfavorite_selector).next().is_some();
+    let thanked = document.select(&thanked_selector).next().is_some();
+    (favorites, favorited, thanked)
+}
+
+/// The reasons a submitted form was rejected, joined into one line.
+fn parse_problem(document: &Html) -> Option<String> {
+    let problem_selector = Selector::parse(".problem").unwrap();
+    let item_selector = Selector::parse("li").unwrap();
+    let problem = document.select(&problem_selector).next()?;
+    let items: Vec<String> = problem
+        .select(&item_selector)
+        .map(|el| el.text().collect::<String>().trim().to_string())
+        .collect();
+    if items.is_empty() {
+        Some(problem.text().collect::<String>().trim().to_string())
+    } else {
+        Some(items.join("；"))
+    }
+}
+
+/// What `/mission/daily` says about today's login reward.
+struct Mission {
+    claimed: bool,
+    /// The token in the redeem button, present until the reward is claimed.
+    once: Option<String>,
+    streak: u32,
+}
+
+fn parse_mission(document: &Html) -> Mission {
+    let button_selector = Selector::parse("input[onclick*='/mission/daily/redeem']").unwrap();
+    let cell_selector = Selector::parse("#Main .cell").unwrap();
+
+    let once = document
+        .select(&button_selector)
+        .next()
+        .and_then(|el| el.value().attr("onclick"))
+        .and_then(|onclick| onclick.split_once("once="))
+        .map(|(_, rest)| rest.chars().take_while(char::is_ascii_digit).collect())
+        .filter(|once: &String| !once.is_empty());
+    let cells: Vec<String> = document
+        .select(&cell_selector)
+        .map(|el| el.text().collect())
+        .collect();
+    // "每日登录奖励已领取" before redeeming again, "已成功领取每日登录奖励" right after.
+    let claimed = cells
+        .iter()
+        .any(|text| text.contains("每日登录奖励已领取") || text.contains("已成功领取"));
+    // "已连续登录 27 天"
+    let streak = cells
+        .iter()
+        .find_map(|text| text.split_once("已连续登录"))
+        .and_then(|(_, rest)| rest.split_whitespace().next())
+        .and_then(|days| days.parse().ok())
+        .unwrap_or_default();
+    Mission {
+        claimed,
+        once,
+        streak,
+    }
+}
+
+/// The coins of the latest daily reward on `/balance`, described there as
+/// "20261018 的每日登录奖励 21 铜币".
+fn parse_daily_reward(document: &Html) -> Option<u32> {
+    let row_selector = Selector::parse("#Main table tr").unwrap();
+    document
+        .select(&row_selector)
+        .map(|row| row.text().collect::<String>())
+        .find_map(|text| {
+            let (before, _) = text.split_once("铜币")?;
+            let (_, amount) = before.rsplit_once("每日登录奖励")?;
+            amount.trim().split('.').next()?.parse().ok()
+        })
+}
+
+/// The profile from a member page, as far as the HTML spells it out.
+fn parse_member(document: &Html, username: &str) -> Member {
+    let gray_selector = Selector::parse("#Main .gray").unwrap();
+    let tagline_selector = Selector::parse("#Main h1 + .bigger").unwrap();
+
+    // "V2EX 第 1 号会员，加入于 2010-04-25 21:45:46 +08:00"
+    let joined = document
+        .select(&gray_selector)
+        .map(|el| el.text().collect::<String>())
+        .find(|text| text.contains("号会员"))
+        .unwrap_or_default();
+    let id = joined
+        .split_once('第')
+        .and_then(|(_, rest)| rest.split_once("号会员"))
+        .and_then(|(id, _)| id.trim().parse().ok())
+        .unwrap_or_default();
+    let created = joined
+        .split_once("加入于")
+        .and_then(|(_, date)| date.split_whitespace().next())
+        .unwrap_or_default()
+        .to_string();
+    let tagline = document
+        .select(&tagline_selector)
+        .next()
+        .map(|el| el.text().collect::<String>().trim().to_string())
+        .unwrap_or_default();
+
+    Member {
+        id,
+        username: username.to_string(),
+        created,
+        tagline,
+        bio: String::new(),
+        website: String::new(),
+        github: String::new(),
+        twitter: String::new(),
+        location: String::new(),
+    }
+}
+
+/// The recent replies on a member page: each `.dock_area` names the topic and
+/// is followed by the reply itself.
+fn parse_member_replies(document: &Html, base_url: &str) -> Vec<MemberReply> {
+    let dock_selector = Selector::parse(".dock_area").unwrap();
+    let content_selector = Selector::parse(".reply_content").unwrap();
+    let topic_selector = Selector::parse("a[href^='/t/']").unwrap();
+    let time_selector = Selector::parse(".fade").unwrap();
+
+    document
+        .select(&dock_selector)
+        .zip(document.select(&content_selector))
+        .filter_map(|(dock, content)| {
+            let topic = dock.select(&topic_selector).next()?;
+            let href = topic.value().attr("href")?;
+            let time = dock
+                .select(&time_selector)
+                .next()
+                .map(|el| el.text().collect::<String>().trim().to_string())
+                .unwrap_or_default();
+            Some(MemberReply {
+                topic_title: topic.text().collect(),
+                topic_link: format!("{}{}", base_url, href),
+                content: content.text().collect::<String>().trim().to_string(),
+                time,
+            })
+        })
+        .collect()
+}
+
+fn parse_notifications(document: &Html, base_url: &str) -> Vec<Notification> {
+    let cell_selector = Selector::parse("#notifications .cell[id^='n_']").unwrap();
+    let text_selector = Selector::parse("span.fade").unwrap();
+    let payload_selector = Selector::parse(".payload").unwrap();
+    let time_selector = Selector::parse(".snow").unwrap();
+
+    document
+        .select(&cell_selector)
+        .filter_map(|cell| {
+            let text = cell.select(&text_selector).next()?;
+            let (kind, member, topic_title, topic_link) =
+                parse_notification_text(&text.html(), base_url);
+            let content = cell
+                .select(&payload_selector)
+                .next()
+                .map(|el| el.text().collect::<String>().trim().to_string())
+                .unwrap_or_default();
+            let time = cell
+                .select(&time_selector)
+                .next()
+                .map(|el| el.text().collect::<String>().trim().to_string())
+                .unwrap_or_default();
+            Some(Notification {
+                kind,
+                member,
+                topic_title,
+                topic_link,
+                content,
+                time,
+            })
+        })
+        .collect()
+}
+
+/// Reads "3 条未读提醒" from the sidebar; logged-out pages have no such link.
+fn parse_unread_count(document: &Html) -> Option<u32> {
+    let link_selector = Selector::parse("a[href^='/notifications']").unwrap();
+    document.select(&link_selector).find_map(|el| {
+        let text = el.text().collect::<String>();
+        let (count, _) = text.split_once("条未读提醒")?;
+        count.trim().parse().ok()
+    })
+}
+
+/// Splits a notification line such as `<a href="/member/x">x</a> 在 <a href="/t/1#reply2">T</a>
+/// 里回复了你` into its kind, member, topic title and topic url on `base_url`.
+fn parse_notification_text(
+    html: &str,
+    base_url: &str,
+) -> (NotificationKind, String, String, String) {
+    let fragment = Html::parse_fragment(html);
+    let member_selector = Selector::parse("a[href^='/member/']").unwrap();
+    let topic_selector = Selector::parse("a[href^='/t/']").unwrap();
+
+    let member = fragment
+        .select(&member_selector)
+        .next()
+        .map(|el| el.text().collect::<String>())
+        .unwrap_or_default();
+    let (title, link) = fragment
+        .select(&topic_selector)
+        .next()
+        .map(|el| {
+            let href = el.value().attr("href").unwrap_or_default();
+            (el.text().collect(), format!("{}{}", base_url, href))
+        })
+        .unwrap_or_default();
+
+    let text = fragment.root_element().text().collect::<String>();
+    let kind = if text.contains("感谢了你") {
+        NotificationKind::Thank
+    } else if text.contains("收藏了你") {
+        NotificationKind::Favorite
+    } else if text.contains("提到了你") {
+        NotificationKind::Mention
+    } else if text.contains("回复了你") {
+        NotificationKind::Reply
+    } else {
+        NotificationKind::Other
+    };
+    (kind, member, title, link)
+}
+
+/// Node headers and similar fields come as HTML snippets; keep only the text.
+fn html_to_text(html: &str) -> String {
+    Html::parse_fragment(html)
+        .root_element()
+        .text()
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+#[test]
+fn test_crawler() {
+    let crawler = Crawler::new();
+    let topic = crawler
+        .fetch_topic_detail("https://www.v2ex.com/t/1111950")
+        .unwrap();
+    println!("{:#?}", topic);
+}
+
+#[test]
+fn test_merge_replies() {
+    let reply = |id: u64, number: &str| Reply {
+        id,
+        author: format!("member{}", id),
+        time: String::new(),
+        content: String::new(),
+        number: number.to_string(),
+        reply_to: String::new(),
+        reply_count: 0,
+        thanks: 0,
+        thanked: false,
+    };
+    let mut replies = vec![
+        reply(3, "2"),
+        reply(0, "?"),
+        reply(1, "1"),
+        reply(0, ""),
+        reply(3, "2"),
+        reply(0, "1"),
+    ];
+    merge_replies(&mut replies);
+    let kept: Vec<(u64, u32)> = replies.iter().map(|r| (r.id, r.floor())).collect();
+    assert_eq!(kept, [(0, 0), (0, 0), (1, 1), (3, 2)]);
+}
+
+#[test]
+fn test_parse_page_count() {
+    let html = r#"<div class="cell"><a href="?p=1" class="page_current">1</a>
+        <a href="?p=2" class="page_normal">2</a><a href="?p=3" class="page_normal">3</a></div>"#;
+    assert_eq!(parse_page_count(&Html::parse_document(html)), 3);
+    assert_eq!(parse_page_count(&Html::parse_document("<div></div>")), 1);
+    assert_eq!(
+        page_url("https://www.v2ex.com/t/1111950#reply45", 2),
+        "https://www.v2ex.com/t/1111950?p=2"
+    );
+}
+
+#[test]
+fn test_parse_topic_list() {
+    let html = r#"<div class="cell item"><table><tr>
+        <td><span class="item_title"><a href="/t/1111950#reply45" class="topic-link">Hello</a></span>
+        <span class="topic_info"><a class="node" href="/go/qna">问与答</a> •
+        <strong><a href="/member/livid">livid</a></strong> •
+        <span title="2025-02-21 10:00:00 +08:00">3 小时前</span></span></td>
+        <td><a href="/t/1111950#reply45" class="count_livid">45</a></td>
+        </tr></table></div><div class="cell">no topic here</div>"#;
+    let topics = parse_topic_list(&Html::parse_document(html), V2EX_URL);
+    assert_eq!(topics.len(), 1);
+    assert_eq!(topics[0].title, "Hello");
+    assert_eq!(topics[0].author, "livid");
+    assert_eq!(topics[0].comment, "45");
+    assert_eq!(topics[0].link, "https://www.v2ex.com/t/1111950#reply45");
+    assert_eq!(topic_id(&topics[0].link), Some(1111950));
+}
+
+#[test]
+fn test_topic_list_pages() {
+    use stub::{Route, StubServer};
+
+    let listing = r#"<div class="cell item"><span class="item_title">
+        <a href="/t/42#reply3">Hello</a></span></div>"#;
+    let server = StubServer::start(vec![Route::new("/go/python", 200, listing)]);
+    let crawler = Crawler::new().with_base_url(&server.url);
+    let feed = Feed::Node {
+        name: "python".to_string(),
+        title: "Python".to_string(),
+    };
+
+    let topics = crawler.fetch_topic_list(&feed, 2).unwrap();
+    assert_eq!(server.request().path, "/go/python?p=2");
+    assert_eq!(topics[0].link, format!("{}/t/42#reply3", server.url));
+    assert!(crawler
+        .fetch_topic_list(&Feed::Tab(Tab::Hot), 2)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_topic_replies_pages() {
+    use stub::{Route, StubServer};
+
+    let reply = r#"{"id": 1, "content": "hi", "member": {"username": "a"}, "created": 1700000000}"#;
+    let page = format!(
+        r#"{{"success": true, "result": [{}]}}"#,
+        vec![reply; 20].join(",")
+    );
+    let server = StubServer::start(vec![Route::new("/api/v2/topics/42/replies", 200, page)]);
+    let crawler = Crawler::new()
+        .with_base_url(&server.url)
+        .with_token(Some("secret".to_string()));
+
+    let replies = crawler
+        .fetch_topic_replies(&format!("{}/t/42", server.url), 2)
+        .unwrap();
+    assert_eq!(server.request().path, "/api/v2/topics/42/replies?p=6");
+    assert_eq!(replies.len(), 100);
+    assert_eq!(replies[0].floor(), 101);
+    assert_eq!(replies[99].floor(), 200);
+}
+
+#[test]
+fn test_parse_notification_text() {
+    let (kind, member, title, link) = parse_notification_text(
+        r#"<a href="/member/livid"><strong>livid</strong></a> 在 <a href="/t/1111950#reply4" class="topic-link">Hello</a> 里回复了你"#,
+        V2EX_URL,
+    );
+    assert_eq!(kind, NotificationKind::Reply);
+    assert_eq!(member, "livid");
+    assert_eq!(title, "Hello");
+    assert_eq!(link, "https://www.v2ex.com/t/1111950#reply4");
+}
+
+#[test]
+fn test_post_reply() {
+    use stub::{Route, StubServer};
+
+    let form = r#"<form method="post" action="/t/1111950"><textarea name="content"></textarea>
+        <input type="hidden" value="31337" name="once" /></form>"#;
+    let server = StubServer::start(vec![Route::new("/t/1111950", 200, form)]);
+    let crawler = Crawler::new();
+    let url = format!("{}/t/1111950#reply3", server.url);
+    crawler.post_reply(&url, "谢谢 & +1").unwrap();
+
+    let page = server.request();
+    assert_eq!(page.method, "GET");
+    assert_eq!(page.path, "/t/1111950");
+    let post = server.request();
+    assert_eq!(post.method, "POST");
+    assert_eq!(post.path, "/t/1111950");
+    assert_eq!(post.body, "content=%E8%B0%A2%E8%B0%A2+%26+%2B1&once=31337");
+    assert_eq!(
+        post.header("referer"),
+        Some(format!("{}/t/1111950", server.url).as_str())
+    );
+
+    let problem = r#"<div class="problem">请解决以下问题然后再提交：<ul><li>你回复过于频繁了</li></ul></div>
+        <input type="hidden" value="31338" name="once" />"#;
+    let server = StubServer::start(vec![Route::new("/t/1", 200, problem)]);
+    let error = crawler
+        .post_reply(&format!("{}/t/1", server.url), "hi")
+        .unwrap_err();
+    assert_eq!(error.message, "你回复过于频繁了");
+
+    let server = StubServer::start(vec![Route::new("/t/1", 200, "<div>登录</div>")]);
+    assert!(crawler
+        .post_reply(&format!("{}/t/1", server.url), "hi")
+        .is_err());
+}
+
+#[test]
+fn test_post_topic() {
+    use stub::{Route, StubServer};
+
+    let form = r#"<form method="post" action="/write"><input type="hidden" value="4242" name="once" /></form>"#;
+    let server = StubServer::start(vec![
+        Route::new("/write?node=", 200, form),
+        Route::new("/write", 302, "").header("Location", "/t/1234567"),
+        Route::new("/t/1234567", 200, "<h1>Hello</h1>"),
+    ]);
+    let crawler = Crawler::new().with_base_url(&server.url);
+    let url = crawler
+        .post_topic("sandbox", "Hello", "**hi**", Syntax::Markdown)
+        .unwrap();
+    assert_eq!(url, format!("{}/t/1234567", server.url));
+
+    assert_eq!(server.request().path, "/write?node=sandbox");
+    let post = server.request();
+    assert_eq!(post.method, "POST");
+    assert_eq!(
+        post.body,
+        "title=Hello&content=**hi**&node_name=sandbox&syntax=markdown&once=4242"
+    );
+    assert_eq!(server.request().path, "/t/1234567");
+}
+
+#[test]
+fn test_topic_actions() {
+    use stub::{Route, StubServer};
+
+    let page = r#"<div class="topic_buttons"><a href="/favorite/topic/42?once=777">加入收藏</a>
+        <div id="topic_thank"><span class="topic_thanked">感谢已发送</span></div></div>
+        <div class="topic_stats">1024 次点击 &nbsp;∙&nbsp; 5 人收藏</div>
+        <div id="r_9001" class="cell"><strong><a href="/member/a">a</a></strong>
+        <span class="ago">1 小时前</span> <span class="small fade"><img src="/heart.png"> 3</span>
+        <div class="thank_area thanked">感谢已发送</div><span class="no">1</span>
+        <div class="reply_content">hi</div></div>"#;
+    let server = StubServer::start(vec![
+        Route::new("/t/42", 200, page),
+        Route::new(
+            "/thank/reply/9001",
+            200,
+            r#"{"success": true, "once": 778}"#,
+        ),
+        Route::new(
+            "/thank/topic/42",
+            200,
+            r#"{"success": false, "message": "你已经感谢过了"}"#,
+        ),
+        Route::new("/favorite/topic/42", 302, "").header("Location", "/t/42"),
+        Route::new("/ignore/topic/42", 302, "").header("Location", "/signin?next=/t/42"),
+        Route::new("/signin", 200, "<form></form>"),
+    ]);
+    let crawler = Crawler::new().with_base_url(&server.url);
+    let url = format!("{}/t/42#reply1", server.url);
+
+    let document = Html::parse_document(page);
+    assert_eq!(parse_once(&document), Some("777".to_string()));
+    assert_eq!(parse_topic_marks(&document), (5, false, true));
+    let replies = parse_replies(&document);
+    assert_eq!((replies[0].id, replies[0].thanks), (9001, 3));
+    assert!(replies[0].thanked);
+
+    crawler.thank_reply(&url, 9001).unwrap();
+    assert_eq!(server.request().path, "/t/42");
+    let thank = server.request();
+    assert_eq!(thank.method, "POST");
+    assert_eq!(thank.path, "/thank/reply/9001?once=777");
+
+    let error = crawler.thank_topic(&url).unwrap_err();
+    assert_eq!(error.message, "你已经感谢过了");
+
+    crawler.favorite_topic(&url, true).unwrap();
+    assert!(crawler.ignore_topic(&url).is_err());
+}
+
+#[test]
+fn test_logged_in_topic_marks() {
+    use stub::{Route, StubServer};
+
+    let topic = r#"[{"id": 42, "title": "Hello", "url": "https://www.v2ex.com/t/42",
+        "replies": 1, "member": {"username": "livid"}}]"#;
+    let page = r#"<h1>Hello</h1><div class="box"><div class="cell">
+        <span class="gray">128 条回复 &nbsp;<strong class="snow">•</strong> &nbsp;2025-02-21</span></div></div>
+        <div class="topic_buttons">
+        <a href="/unfavorite/topic/42?once=777">取消收藏</a></div>
+        <div class="topic_stats">1024 次点击 &nbsp;∙&nbsp; 5 人收藏</div>
+        <div id="r_9001" class="cell"><strong><a href="/member/a">a</a></strong>
+        <div class="thank_area thanked">感谢已发送</div><span class="no">1</span>
+        <div class="reply_content">hi</div></div>"#;
+    let server = StubServer::start(vec![
+        Route::new("/api/topics/show.json", 200, topic),
+        Route::new("/api/replies/show.json", 200, "[]"),
+        Route::new("/t/42", 200, page),
+        Route::new("/unfavorite/topic/42", 302, "").header("Location", "/t/42"),
+    ]);
+    let crawler = Crawler::new()
+        .with_base_url(&server.url)
+        .with_session(Session::load(Some("A2=secret".to_string())))
+        .with_source(Source::Api);
+    let url = format!("{}/t/42", server.url);
+
+    let detail = crawler.fetch_topic_detail(&url).unwrap();
+    assert_eq!(server.request().path, "/t/42");
+    assert_eq!((detail.favorites, detail.favorited), (5, true));
+    assert_eq!(detail.comment, "128");
+    assert!(detail.replies[0].thanked);
+
+    // What `f` on the detail page asks for.
+    crawler.favorite_topic(&url, !detail.favorited).unwrap();
+    assert_eq!(server.request().path, "/t/42");
+    assert_eq!(server.request().path, "/unfavorite/topic/42?once=777");
+}
+
+#[test]
+fn test_notifications() {
+    use stub::{Route, StubServer};
+
+    let page = r#"<div id="notifications"><div class="cell" id="n_1"><table><tr><td>
+        <span class="fade"><a href="/member/livid"><strong>livid</strong></a> 感谢了你在主题
+        › <a href="/t/1111950#reply3">Hello</a> 里的回复</span> &nbsp; <span class="snow">1 小时前</span>
+        <div class="payload">谢谢</div></td></tr></table></div></div>"#;
+    let home = r#"<div id="Rightbar"><a href="/notifications" class="fade">3 条未读提醒</a></div>"#;
+    let server = StubServer::start(vec![
+        Route::new("/notifications", 200, page),
+        Route::new("/", 200, home),
+    ]);
+    let crawler = Crawler::new().with_base_url(&server.url);
+
+    let notifications = crawler.fetch_notifications(2).unwrap();
+    assert_eq!(server.request().path, "/notifications?p=2");
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].kind, NotificationKind::Thank);
+    assert_eq!(notifications[0].member, "livid");
+    assert_eq!(notifications[0].topic_title, "Hello");
+    assert_eq!(notifications[0].content, "谢谢");
+    assert_eq!(notifications[0].time, "1 小时前");
+    assert_eq!(reply_floor(&notifications[0].topic_link), Some(3));
+
+    assert_eq!(crawler.fetch_unread_count().unwrap(), 3);
+    let logged_out = Html::parse_document(r#"<a href="/signin">登录</a>"#);
+    assert_eq!(parse_unread_count(&logged_out), None);
+}
+
+#[test]
+fn test_fetch_member() {
+    use stub::{Route, StubServer};
+
+    let page = r#"<div id="Main"><div class="box"><h1>livid</h1><span class="bigger">Remember the bigger green</span>
+        <span class="gray">V2EX 第 1 号会员，加入于 2010-04-25 21:45:46 +08:00</span></div>
+        <div class="cell item"><span class="item_title"><a href="/t/1#reply2">Hello</a></span>
+        <span class="topic_info"><strong><a href="/member/livid">livid</a></strong></span></div>
+        <div class="dock_area"><div class="fr"><span class="fade">3 小时前</span></div>
+        <span class="gray">回复了 <a href="/member/a">a</a> 创建的主题 › <a href="/t/2#reply7">World</a></span></div>
+        <div class="inner"><div class="reply_content">+1</div></div></div>"#;
+    let server = StubServer::start(vec![
+        Route::new("/member/livid", 200, page),
+        Route::new("/api/members/show.json", 500, ""),
+    ]);
+    let crawler = Crawler::new().with_base_url(&server.url);
+
+    let profile = crawler.fetch_member("livid").unwrap();
+    assert_eq!(profile.member.id, 1);
+    assert_eq!(profile.member.created, "2010-04-25");
+    assert_eq!(profile.member.tagline, "Remember the bigger green");
+    assert_eq!(profile.topics.len(), 1);
+    assert_eq!(profile.topics[0].title, "Hello");
+    assert_eq!(profile.replies.len(), 1);
+    assert_eq!(profile.replies[0].topic_title, "World");
+    assert_eq!(profile.replies[0].content, "+1");
+    assert_eq!(profile.replies[0].time, "3 小时前");
+    assert_eq!(reply_floor(&profile.replies[0].topic_link), Some(7));
+}
+
+#[test]
+fn test_check_in() {
+    use stub::{Route, StubServer};
+
+    let unclaimed = r#"<div id="Main"><div class="box"><div class="cell">
+        <input type="button" class="super normal button" value="领取 X 铜币" onclick="location.href = '/mission/daily/redeem?once=4242';"></div>
+        <div class="cell">已连续登录 26 天</div></div></div>"#;
+    let claimed = r#"<div id="Main"><div class="box"><div class="cell">已成功领取每日登录奖励</div>
+        <div class="cell">已连续登录 27 天</div></div></div>"#;
+    let balance = r#"<div id="Main"><table><tr><th>时间</th><th>类型</th><th>数额</th></tr>
+        <tr><td>2026-10-18 08:00:00</td><td>每日登录奖励</td><td>21.0</td>
+        <td>20261018 的每日登录奖励 21 铜币</td></tr></table></div>"#;
+    let server = StubServer::start(vec![
+        Route::new("/mission/daily", 200, unclaimed),
+        Route::new("/mission/daily/redeem", 200, claimed),
+        Route::new("/balance", 200, balance),
+    ]);
+    let crawler = Crawler::new().with_base_url(&server.url);
+
+    let check_in = crawler.check_in().unwrap();
+    assert_eq!(server.request().path, "/mission/daily");
+    assert_eq!(server.request().path, "/mission/daily/redeem?once=4242");
+    assert_eq!(
+        check_in,
+        CheckIn {
+            claimed: true,
+            coins: Some(21),
+            streak: 27
+        }
+    );
+
+    let mission = parse_mission(&Html::parse_document(
+        r#"<div id="Main"><div class="cell"><span class="gray">每日登录奖励已领取</span></div>
+        <div class="cell">已连续登录 27 天</div></div>"#,
+    ));
+    assert!(mission.claimed);
+    assert_eq!((mission.once, mission.streak), (None, 27));
+}
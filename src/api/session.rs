@@ -0,0 +1,131 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use reqwest::{cookie::CookieStore, cookie::Jar, Url};
+use scraper::{Html, Selector};
+
+use crate::config::data_dir;
+
+/// Who the cookie session belongs to, as far as V2EX is concerned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionStatus {
+    Anonymous,
+    LoggedIn(String),
+    /// A cookie was configured but V2EX no longer accepts it.
+    Expired,
+}
+
+/// A V2EX login cookie (`A2`, `PB3_SESSION`), taken from the config or from the
+/// copy saved after the last successful check.
+#[derive(Debug, Clone)]
+pub struct Session {
+    cookie: Option<String>,
+    path: PathBuf,
+}
+
+impl Session {
+    /// Uses the configured cookie when there is one and the saved session otherwise.
+    pub fn load(configured: Option<String>) -> Self {
+        let path = data_dir().join("session");
+        let cookie = configured
+            .or_else(|| fs::read_to_string(&path).ok())
+            .map(|cookie| cookie.trim().to_string())
+            .filter(|cookie| !cookie.is_empty());
+        Self { cookie, path }
+    }
+
+    pub fn has_cookie(&self) -> bool {
+        self.cookie.is_some()
+    }
+
+    /// Puts every `name=value` pair of the cookie into the jar for `url`'s domain.
+    pub fn apply(&self, jar: &Jar, url: &Url) {
+        let Some(cookie) = &self.cookie else {
+            return;
+        };
+        let domain = url
+            .host_str()
+            .map(|host| host.trim_start_matches("www."))
+            .unwrap_or_default();
+        for pair in cookie.split(';').map(str::trim).filter(|p| p.contains('=')) {
+            jar.add_cookie_str(&format!("{}; Domain={}; Path=/", pair, domain), url);
+        }
+    }
+
+    /// Writes the cookies the jar now holds for `url`, which V2EX may have refreshed.
+    pub fn save(&self, jar: &Jar, url: &Url) -> io::Result<()> {
+        let Some(cookies) = jar.cookies(url) else {
+            return Ok(());
+        };
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        // Only the owner may read the cookie, from the moment the file exists.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(&self.path)?;
+        // A file left by an older version may still be readable by others.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(fs::Permissions::from_mode(0o600))?;
+        }
+        file.write_all(cookies.as_bytes())
+    }
+}
+
+/// The username in the top bar, which V2EX only shows to logged-in members.
+pub fn parse_username(document: &Html) -> Option<String> {
+    let selector = Selector::parse("#Top .tools a[href^='/member/']").unwrap();
+    document
+        .select(&selector)
+        .next()
+        .and_then(|el| el.value().attr("href"))
+        .map(|href| href.trim_start_matches("/member/").to_string())
+        .filter(|name| !name.is_empty())
+}
+
+#[test]
+fn test_parse_username() {
+    let logged_in = r#"<div id="Top"><div class="tools"><a href="/" class="top">首页</a>
+        <a href="/member/livid" class="top">livid</a><a href="/notes" class="top">记事本</a></div></div>"#;
+    let anonymous = r#"<div id="Top"><div class="tools"><a href="/" class="top">首页</a>
+        <a href="/signup" class="top">注册</a><a href="/signin" class="top">登录</a></div></div>"#;
+    assert_eq!(
+        parse_username(&Html::parse_document(logged_in)),
+        Some("livid".to_string())
+    );
+    assert_eq!(parse_username(&Html::parse_document(anonymous)), None);
+}
+
+#[test]
+fn test_session_cookie_jar() {
+    let url = Url::parse("https://www.v2ex.com/").unwrap();
+    let jar = Jar::default();
+    let session = Session {
+        cookie: Some("A2=token; PB3_SESSION=\"2|1:0\"".to_string()),
+        path: std::env::temp_dir().join(format!("lazyv2ex-session-{}", std::process::id())),
+    };
+    session.apply(&jar, &url);
+    let cookies = jar.cookies(&url).unwrap();
+    assert!(cookies.to_str().unwrap().contains("A2=token"));
+
+    session.save(&jar, &url).unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(&session.path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+    let saved = fs::read_to_string(&session.path).unwrap();
+    fs::remove_file(&session.path).unwrap();
+    assert!(saved.contains("PB3_SESSION"));
+}
@@ -0,0 +1,287 @@
+use chrono::DateTime;
+use serde::{de::DeserializeOwned, Deserialize};
+
+use super::{count_replies, html_to_text, FetchError, FetchResult};
+use crate::i18n::msg;
+use crate::model::{Member, Node, Reply, Topic, TopicDetail};
+use crate::time::time_formatting::format_relative_time;
+
+pub const V2EX_API_URL: &str = "https://www.v2ex.com/api";
+
+/// Typed client for the public V2EX API v1.
+#[derive(Clone)]
+pub struct V1Client {
+    client: reqwest::blocking::Client,
+    base_url: String,
+}
+
+impl V1Client {
+    /// `base_url` is the API root, e.g. `https://www.v2ex.com/api`.
+    pub fn new(client: reqwest::blocking::Client, base_url: impl Into<String>) -> Self {
+        Self {
+            client,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub fn hot_topics(&self) -> FetchResult<Vec<Topic>> {
+        let topics: Vec<TopicJson> = self.get_json("/topics/hot.json")?;
+        Ok(topics.into_iter().map(Topic::from).collect())
+    }
+
+    pub fn latest_topics(&self) -> FetchResult<Vec<Topic>> {
+        let topics: Vec<TopicJson> = self.get_json("/topics/latest.json")?;
+        Ok(topics.into_iter().map(Topic::from).collect())
+    }
+
+    /// The topic together with all of its replies.
+    pub fn topic(&self, id: u64) -> FetchResult<TopicDetail> {
+        let path = format!("/topics/show.json?id={}", id);
+        let topics: Vec<TopicJson> = self.get_json(&path)?;
+        let topic = topics
+            .into_iter()
+            .next()
+            .ok_or_else(|| FetchError::new(&self.url(&path), msg().topic_missing))?;
+
+        Ok(TopicDetail {
+            title: topic.title,
+            content: topic.content,
+            author: topic.member.username,
+            comment: topic.replies.to_string(),
+            updated: relative_time(topic.last_touched.max(topic.created)),
+            link: topic.url,
+            replies: self.replies(id)?,
+            page_count: 1,
+            favorites: 0,
+            favorited: false,
+            thanked: false,
+        })
+    }
+
+    pub fn replies(&self, topic_id: u64) -> FetchResult<Vec<Reply>> {
+        let replies: Vec<ReplyJson> =
+            self.get_json(&format!("/replies/show.json?topic_id={}", topic_id))?;
+
+        let mut replies: Vec<Reply> = replies
+            .into_iter()
+            .enumerate()
+            .map(|(index, reply)| {
+                let reply_to = reply
+                    .content
+                    .trim()
+                    .strip_prefix('@')
+                    .and_then(|rest| rest.split_whitespace().next())
+                    .unwrap_or_default()
+                    .to_string();
+                Reply {
+                    id: reply.id,
+                    author: reply.member.username,
+                    time: relative_time(reply.created),
+                    content: reply.content,
+                    number: (index + 1).to_string(),
+                    reply_to,
+                    reply_count: 0,
+                    thanks: reply.thanks,
+                    thanked: false,
+                }
+            })
+            .collect();
+        count_replies(&mut replies);
+        Ok(replies)
+    }
+
+    pub fn nodes(&self) -> FetchResult<Vec<Node>> {
+        let nodes: Vec<NodeJson> = self.get_json("/nodes/all.json")?;
+        Ok(nodes.into_iter().map(Node::from).collect())
+    }
+
+    pub fn node(&self, name: &str) -> FetchResult<Node> {
+        let node: NodeJson = self.get_json(&format!("/nodes/show.json?name={}", name))?;
+        Ok(node.into())
+    }
+
+    pub fn member(&self, username: &str) -> FetchResult<Member> {
+        let member: MemberJson =
+            self.get_json(&format!("/members/show.json?username={}", username))?;
+        Ok(Member {
+            id: member.id,
+            username: member.username,
+            created: DateTime::from_timestamp(member.created, 0)
+                .map(|t| t.format("%Y-%m-%d").to_string())
+                .unwrap_or_default(),
+            tagline: member.tagline.unwrap_or_default(),
+            bio: member.bio.unwrap_or_default(),
+            website: member.website.unwrap_or_default(),
+            github: member.github.unwrap_or_default(),
+            twitter: member.twitter.unwrap_or_default(),
+            location: member.location.unwrap_or_default(),
+        })
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    fn get_json<T: DeserializeOwned>(&self, path: &str) -> FetchResult<T> {
+        let url = self.url(path);
+        let resp = self
+            .client
+            .get(&url)
+            .send()
+            .and_then(|resp| resp.error_for_status())
+            .map_err(|e| FetchError::from_reqwest(&url, e))?;
+        // v1 reports failures such as unknown members as `{"status": "error", ...}`.
+        let value: serde_json::Value =
+            resp.json().map_err(|e| FetchError::from_reqwest(&url, e))?;
+        if value.get("status").and_then(|s| s.as_str()) == Some("error") {
+            let message = value
+                .get("message")
+                .and_then(|m| m.as_str())
+                .unwrap_or("error");
+            return Err(FetchError::new(&url, message));
+        }
+        serde_json::from_value(value).map_err(|e| FetchError::new(&url, e))
+    }
+}
+
+fn relative_time(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(format_relative_time)
+        .unwrap_or_default()
+}
+
+#[derive(Deserialize)]
+struct MemberRef {
+    username: String,
+}
+
+#[derive(Deserialize)]
+struct TopicJson {
+    title: String,
+    url: String,
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    replies: u32,
+    member: MemberRef,
+    #[serde(default)]
+    created: i64,
+    #[serde(default)]
+    last_touched: i64,
+}
+
+impl From<TopicJson> for Topic {
+    fn from(topic: TopicJson) -> Self {
+        Topic::new(
+            topic.title,
+            topic.member.username,
+            topic.replies.to_string(),
+            topic.content,
+            relative_time(topic.last_touched.max(topic.created)),
+            topic.url,
+        )
+    }
+}
+
+#[derive(Deserialize)]
+struct ReplyJson {
+    #[serde(default)]
+    id: u64,
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    thanks: u32,
+    member: MemberRef,
+    #[serde(default)]
+    created: i64,
+}
+
+#[derive(Deserialize)]
+struct NodeJson {
+    name: String,
+    title: String,
+    #[serde(default)]
+    topics: u32,
+    header: Option<String>,
+}
+
+impl From<NodeJson> for Node {
+    fn from(node: NodeJson) -> Self {
+        Node {
+            name: node.name,
+            title: node.title,
+            topics: node.topics,
+            header: node.header.as_deref().map(html_to_text).unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct MemberJson {
+    id: u64,
+    username: String,
+    #[serde(default)]
+    created: i64,
+    tagline: Option<String>,
+    bio: Option<String>,
+    website: Option<String>,
+    github: Option<String>,
+    twitter: Option<String>,
+    location: Option<String>,
+}
+
+#[test]
+fn test_v1_client() {
+    use super::stub::{Route, StubServer};
+
+    let topic = r#"[{"id": 1111950, "title": "Hello", "url": "https://www.v2ex.com/t/1111950",
+        "content": "body", "replies": 2, "member": {"username": "livid"},
+        "node": {"name": "qna"}, "created": 1700000000, "last_touched": 1700000100}]"#;
+    let replies = r#"[{"id": 1, "content": "first", "member": {"username": "a"}, "created": 1700000000},
+        {"id": 2, "content": "@a thanks", "member": {"username": "b"}, "created": 1700000050}]"#;
+    let server = StubServer::start(vec![
+        Route::new("/api/topics/show.json", 200, topic),
+        Route::new("/api/replies/show.json", 200, replies),
+        Route::new(
+            "/api/nodes/show.json",
+            200,
+            r#"{"name": "python", "title": "Python", "topics": 42, "header": "这里讨论 <a href=\"/go/flask\">Flask</a> 等"}"#,
+        ),
+        Route::new(
+            "/api/members/show.json",
+            200,
+            r#"{"status": "error", "message": "Member not found"}"#,
+        ),
+    ]);
+    let client = V1Client::new(
+        reqwest::blocking::Client::new(),
+        format!("{}/api/", server.url),
+    );
+
+    let detail = client.topic(1111950).unwrap();
+    assert_eq!(detail.title, "Hello");
+    assert_eq!(detail.author, "livid");
+    assert_eq!(detail.replies.len(), 2);
+    assert_eq!(detail.replies[1].number, "2");
+    assert_eq!(detail.replies[1].reply_to, "a");
+    assert_eq!(detail.replies[0].reply_count, 1);
+    assert_eq!(detail.replies[1].id, 2);
+
+    let request = server.request();
+    assert_eq!(request.method, "GET");
+    assert_eq!(request.path, "/api/topics/show.json?id=1111950");
+    assert!(request.header("host").is_some());
+    assert!(request.body.is_empty());
+    assert_eq!(
+        server.request().path,
+        "/api/replies/show.json?topic_id=1111950"
+    );
+
+    let node = client.node("python").unwrap();
+    assert_eq!(server.request().path, "/api/nodes/show.json?name=python");
+    assert_eq!((node.title.as_str(), node.topics), ("Python", 42));
+    assert_eq!(node.header, "这里讨论 Flask 等");
+
+    let error = client.member("nobody").unwrap_err();
+    assert_eq!(error.message, "Member not found");
+}
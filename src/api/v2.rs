@@ -0,0 +1,310 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::DateTime;
+use reqwest::{blocking::Response, header::HeaderMap, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize};
+
+use super::{count_replies, parse_notification_text, FetchError, FetchResult};
+use crate::i18n::msg;
+use crate::model::{Member, Notification, Reply, Topic};
+use crate::time::time_formatting::format_relative_time;
+
+/// Items per page of every paginated v2 endpoint.
+pub const PAGE_SIZE: u32 = 20;
+
+/// The quota reported by the `X-Rate-Limit-*` headers of the last v2 response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub limit: Option<u32>,
+    pub remaining: u32,
+}
+
+/// Client for V2EX API v2, authenticated with a personal access token.
+#[derive(Clone)]
+pub struct V2Client {
+    client: reqwest::blocking::Client,
+    base_url: String,
+    token: String,
+    rate_limit: Arc<Mutex<Option<RateLimit>>>,
+}
+
+impl V2Client {
+    /// `base_url` is the API root, e.g. `https://www.v2ex.com/api/v2`.
+    pub fn new(
+        client: reqwest::blocking::Client,
+        base_url: impl Into<String>,
+        token: impl Into<String>,
+    ) -> Self {
+        Self {
+            client,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            token: token.into(),
+            rate_limit: Arc::default(),
+        }
+    }
+
+    /// The quota left after the most recent request, once one has been made.
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        *self.rate_limit.lock().unwrap()
+    }
+
+    /// The member the token belongs to.
+    pub fn member(&self) -> FetchResult<Member> {
+        let member: MemberJson = self.get_json("/member")?;
+        Ok(Member {
+            id: member.id,
+            username: member.username,
+            created: DateTime::from_timestamp(member.created, 0)
+                .map(|t| t.format("%Y-%m-%d").to_string())
+                .unwrap_or_default(),
+            tagline: member.tagline.unwrap_or_default(),
+            bio: member.bio.unwrap_or_default(),
+            website: member.website.unwrap_or_default(),
+            github: member.github.unwrap_or_default(),
+            twitter: member.twitter.unwrap_or_default(),
+            location: member.location.unwrap_or_default(),
+        })
+    }
+
+    pub fn notifications(&self, page: u32) -> FetchResult<Vec<Notification>> {
+        let notifications: Vec<NotificationJson> =
+            self.get_json(&format!("/notifications?p={}", page))?;
+        Ok(notifications
+            .into_iter()
+            .map(|notification| {
+                let (kind, member, topic_title, topic_link) =
+                    parse_notification_text(&notification.text, self.site_url());
+                Notification {
+                    kind,
+                    member,
+                    topic_title,
+                    topic_link,
+                    content: notification.payload.unwrap_or_default(),
+                    time: relative_time(notification.created),
+                }
+            })
+            .collect())
+    }
+
+    pub fn node_topics(&self, name: &str, page: u32) -> FetchResult<Vec<Topic>> {
+        let topics: Vec<TopicJson> =
+            self.get_json(&format!("/nodes/{}/topics?p={}", name, page))?;
+        Ok(topics
+            .into_iter()
+            .map(|topic| {
+                Topic::new(
+                    topic.title,
+                    topic.member.map(|m| m.username).unwrap_or_default(),
+                    topic.replies.to_string(),
+                    topic.content,
+                    relative_time(topic.last_touched.max(topic.created)),
+                    format!("{}/t/{}", self.site_url(), topic.id),
+                )
+            })
+            .collect())
+    }
+
+    pub fn topic_replies(&self, topic_id: u64, page: u32) -> FetchResult<Vec<Reply>> {
+        let replies: Vec<ReplyJson> =
+            self.get_json(&format!("/topics/{}/replies?p={}", topic_id, page))?;
+        let first_floor = (page.max(1) - 1) * PAGE_SIZE + 1;
+        let mut replies: Vec<Reply> = replies
+            .into_iter()
+            .enumerate()
+            .map(|(index, reply)| Reply {
+                id: reply.id,
+                reply_to: reply
+                    .content
+                    .trim()
+                    .strip_prefix('@')
+                    .and_then(|rest| rest.split_whitespace().next())
+                    .unwrap_or_default()
+                    .to_string(),
+                author: reply.member.username,
+                time: relative_time(reply.created),
+                content: reply.content,
+                number: (first_floor + index as u32).to_string(),
+                reply_count: 0,
+                thanks: reply.thanks,
+                thanked: false,
+            })
+            .collect();
+        count_replies(&mut replies);
+        Ok(replies)
+    }
+
+    /// The site this API belongs to, which topic links point at.
+    fn site_url(&self) -> &str {
+        self.base_url
+            .strip_suffix("/api/v2")
+            .unwrap_or(&self.base_url)
+    }
+
+    fn get_json<T: DeserializeOwned>(&self, path: &str) -> FetchResult<T> {
+        let url = format!("{}{}", self.base_url, path);
+        let resp = self
+            .client
+            .get(&url)
+            .bearer_auth(&self.token)
+            .send()
+            .map_err(|e| FetchError::from_reqwest(&url, e))?;
+        self.record_rate_limit(resp.headers());
+        parse_envelope(&url, resp)
+    }
+
+    fn record_rate_limit(&self, headers: &HeaderMap) {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse::<u32>().ok())
+        };
+        if let Some(remaining) = header("X-Rate-Limit-Remaining") {
+            *self.rate_limit.lock().unwrap() = Some(RateLimit {
+                limit: header("X-Rate-Limit-Limit"),
+                remaining,
+            });
+        }
+    }
+}
+
+/// Unwraps `{"success": ..., "message": ..., "result": ...}`, turning failures into errors.
+fn parse_envelope<T: DeserializeOwned>(url: &str, resp: Response) -> FetchResult<T> {
+    let status = resp.status();
+    let envelope: Envelope<T> = resp.json().map_err(|e| FetchError {
+        url: url.to_string(),
+        status: Some(status.as_u16()),
+        message: e.to_string(),
+    })?;
+    let message = envelope.message.unwrap_or_else(|| status.to_string());
+
+    match envelope.result {
+        Some(result) if envelope.success && status.is_success() => Ok(result),
+        _ if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN => {
+            Err(FetchError {
+                url: url.to_string(),
+                status: Some(status.as_u16()),
+                message: (msg().token_invalid)(&message),
+            })
+        }
+        _ => Err(FetchError {
+            url: url.to_string(),
+            status: Some(status.as_u16()),
+            message,
+        }),
+    }
+}
+
+fn relative_time(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(format_relative_time)
+        .unwrap_or_default()
+}
+
+#[derive(Deserialize)]
+struct Envelope<T> {
+    #[serde(default)]
+    success: bool,
+    message: Option<String>,
+    result: Option<T>,
+}
+
+#[derive(Deserialize)]
+struct MemberRef {
+    username: String,
+}
+
+#[derive(Deserialize)]
+struct MemberJson {
+    id: u64,
+    username: String,
+    #[serde(default)]
+    created: i64,
+    tagline: Option<String>,
+    bio: Option<String>,
+    website: Option<String>,
+    github: Option<String>,
+    twitter: Option<String>,
+    location: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct NotificationJson {
+    text: String,
+    payload: Option<String>,
+    #[serde(default)]
+    created: i64,
+}
+
+#[derive(Deserialize)]
+struct TopicJson {
+    id: u64,
+    title: String,
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    replies: u32,
+    member: Option<MemberRef>,
+    #[serde(default)]
+    created: i64,
+    #[serde(default)]
+    last_touched: i64,
+}
+
+#[derive(Deserialize)]
+struct ReplyJson {
+    #[serde(default)]
+    id: u64,
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    thanks: u32,
+    member: MemberRef,
+    #[serde(default)]
+    created: i64,
+}
+
+#[test]
+fn test_v2_client() {
+    use super::stub::{Route, StubServer};
+
+    let server = StubServer::start(vec![
+        Route::new(
+            "/api/v2/member",
+            200,
+            r#"{"success": true, "message": "", "result": {"id": 1, "username": "livid", "created": 1272203146}}"#,
+        )
+        .header("X-Rate-Limit-Limit", "600")
+        .header("X-Rate-Limit-Remaining", "599"),
+        Route::new(
+            "/api/v2/nodes/python/topics",
+            401,
+            r#"{"success": false, "message": "Token expired"}"#,
+        ),
+    ]);
+    let client = V2Client::new(
+        reqwest::blocking::Client::new(),
+        format!("{}/api/v2", server.url),
+        "secret",
+    );
+
+    assert_eq!(client.rate_limit(), None);
+    let member = client.member().unwrap();
+    assert_eq!(member.username, "livid");
+    assert_eq!(member.created, "2010-04-25");
+    assert_eq!(
+        client.rate_limit(),
+        Some(RateLimit {
+            limit: Some(600),
+            remaining: 599
+        })
+    );
+    assert_eq!(
+        server.request().header("authorization"),
+        Some("Bearer secret")
+    );
+
+    let error = client.node_topics("python", 1).unwrap_err();
+    assert_eq!(error.status, Some(401));
+    assert!(error.message.contains("Token expired"));
+}
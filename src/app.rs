@@ -1,87 +1,349 @@
+use std::{
+    rc::Rc,
+    sync::mpsc::{self, Receiver},
+};
+
 use crossterm::event::{Event, KeyCode, KeyModifiers};
 
 use crate::{
     action::Action,
-    pages::{Page, PageType},
+    api::Crawler,
+    config::Config,
+    i18n::msg,
+    keymap::{Command, Key, Keymap, Lookup},
+    pages::{
+        detail::DetailPage,
+        help::Help,
+        member::MemberPage,
+        palette::{self, Palette},
+        Page,
+    },
+    router::{Route, Router},
+    store::commands::CommandHistory,
+    theme::{ColorSupport, Theme},
+    worker::Worker,
 };
 
 pub struct App {
-    current_page: PageType,
-    pages: Vec<Box<dyn Page>>,
+    router: Router,
+    // What a topic or member page is made with, since each visit gets its own.
+    worker: Worker,
+    scroll_step: u16,
+    keymap: Rc<Keymap>,
+    theme: Theme,
+    // The start of a key sequence such as `gg`, waiting for the rest.
+    pending_keys: Vec<Key>,
+    // Shown over the current page, taking every key until closed.
+    help: Option<Help>,
+    // The `:` line; topic ids typed there become urls on `base_url`.
+    palette: Palette,
+    base_url: String,
+    receiver: Receiver<Action>,
 }
 
 impl App {
-    pub fn new() -> Self {
+    pub fn new(crawler: Crawler, config: &Config) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let worker = Worker::new(crawler, sender);
+        let keymap = Rc::new(config.keys.clone());
         let pages: Vec<Box<dyn Page>> = vec![
-            Box::new(crate::pages::home::HomePage::new()),
-            Box::new(crate::pages::detail::DetailPage::new()),
+            Box::new(crate::pages::home::HomePage::new(
+                worker.clone(),
+                keymap.clone(),
+                config.ui.unread_poll_interval(),
+            )),
+            Box::new(crate::pages::nodes::NodesPage::new(
+                worker.clone(),
+                keymap.clone(),
+            )),
+            Box::new(crate::pages::new_topic::NewTopicPage::new(worker.clone())),
+            Box::new(crate::pages::notifications::NotificationsPage::new(
+                worker.clone(),
+                keymap.clone(),
+            )),
+            Box::new(crate::pages::bookmarks::BookmarksPage::new(
+                worker.clone(),
+                keymap.clone(),
+            )),
+            Box::new(crate::pages::history::HistoryPage::new(keymap.clone())),
         ];
 
         App {
-            current_page: PageType::Home,
-            pages,
+            router: Router::new(pages),
+            worker,
+            scroll_step: config.ui.scroll_step,
+            keymap,
+            theme: config.theme.clone().for_terminal(ColorSupport::detect()),
+            pending_keys: vec![],
+            help: None,
+            palette: Palette::new(CommandHistory::load()),
+            base_url: config.network.base_url.clone(),
+            receiver,
         }
     }
 
+    /// Returns the next result reported by the background worker, if any.
+    pub fn try_recv(&self) -> Option<Action> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Starts the home page, which is where every run begins.
+    pub fn init(&mut self) -> Option<Action> {
+        self.router.page().init()
+    }
+
     pub fn render(&mut self, frame: &mut ratatui::Frame) {
-        for page in &mut self.pages {
-            if page.page_type() == self.current_page {
-                page.render(frame);
-                break;
-            }
+        self.router.page().render(frame, &self.theme);
+        if let Some(help) = &mut self.help {
+            help.render(frame, &self.theme);
         }
+        self.palette.render(frame, &self.theme);
     }
 
     pub fn handle_event(&mut self, event: Event) -> Option<Action> {
         if let Event::Key(key_event) = event {
-            if key_event.code == KeyCode::Char('q') {
-                return Some(Action::Quit);
-            }
             if key_event.code == KeyCode::Char('c') && key_event.modifiers == KeyModifiers::CONTROL
             {
                 return Some(Action::Quit);
             }
         }
-        for page in &mut self.pages {
-            if page.page_type() == self.current_page {
-                return page.handle_event(event);
+        if let Some(help) = &mut self.help {
+            if let Event::Key(key_event) = event {
+                if !help.handle_key(key_event) {
+                    self.help = None;
+                }
             }
+            return None;
         }
-        None
-    }
-
-    pub fn update(&mut self, action: Action) -> Option<Action> {
-        if let Action::OpenBrowser(url) = action {
-            if let Err(e) = open::that(&url) {
-                eprintln!("Failed to open URL: {}", e);
+        if let Event::Key(key_event) = event {
+            if self.palette.is_open() {
+                let command = self.palette.handle_key(key_event)?;
+                return self.run_command(&command);
             }
-            return None;
+            self.palette.dismiss();
         }
-        for page in &mut self.pages {
-            if page.page_type() == self.current_page {
-                match action {
-                    Action::Enter => {
-                        self.current_page = PageType::Detail;
+        let page = self.router.page();
+        let context = page.context().filter(|_| !page.captures_input());
+        let (key_event, context) = match (&event, context) {
+            (Event::Key(key_event), Some(context)) => (*key_event, context),
+            _ => {
+                self.pending_keys.clear();
+                return page.handle_event(event);
+            }
+        };
+        self.pending_keys.push(Key::from(key_event));
+        match self.keymap.lookup(context, &self.pending_keys) {
+            Lookup::Command(command) => {
+                self.pending_keys.clear();
+                match command {
+                    Command::Quit => Some(Action::Quit),
+                    Command::Help => {
+                        self.help = Some(Help::new(&self.keymap, context));
+                        None
                     }
-                    Action::GoHome => {
-                        self.current_page = PageType::Home;
+                    Command::Palette => {
+                        self.palette.open();
+                        None
                     }
-                    _ => {}
+                    Command::Forward => Some(Action::GoForward),
+                    command => page.handle_command(command),
                 }
-                let next_action = page.update(action);
-                return next_action;
+            }
+            Lookup::Prefix => None,
+            // A sequence that went nowhere; the last key may still mean something alone.
+            Lookup::None if self.pending_keys.len() > 1 => {
+                self.pending_keys.clear();
+                self.handle_event(event)
+            }
+            Lookup::None => {
+                self.pending_keys.clear();
+                page.handle_event(event)
             }
         }
-        None
     }
 
-    pub fn switch_page(&mut self, page_type: PageType) -> Option<Action> {
-        self.current_page = page_type;
-        for page in &mut self.pages {
-            if page.page_type() == self.current_page {
-                return page.init();
+    /// Runs a `:` command, typed or given on the command line. One that does
+    /// not parse stays on the `:` line with what is wrong with it.
+    pub fn run_command(&mut self, command: &str) -> Option<Action> {
+        let result = palette::parse(command, &self.base_url).and_then(|action| match action {
+            Action::ExportTopic { .. } if !matches!(self.router.route(), Route::Detail(_)) => {
+                Err(msg().export_no_topic.to_string())
+            }
+            action => Ok(action),
+        });
+        match result {
+            Ok(action) => {
+                self.palette.finish(command);
+                Some(action)
+            }
+            Err(error) => {
+                self.palette.fail(command, error);
+                None
+            }
+        }
+    }
+
+    pub fn update(&mut self, action: Action) -> Option<Action> {
+        match action {
+            Action::OpenBrowser(url) => {
+                if let Err(e) = open::that(&url) {
+                    eprintln!("Failed to open URL: {}", e);
+                }
+                return None;
+            }
+            Action::SetTheme(preset) => {
+                self.theme = Theme::preset(preset).for_terminal(ColorSupport::detect());
+                return None;
             }
+            Action::SelectTab(_) | Action::OpenNode { .. } | Action::FetchNode(_) => {
+                return self.push(Route::Home).update(action)
+            }
+            // Background results belong to the page that asked for them,
+            // which is not necessarily the one on screen.
+            Action::TopicsLoaded(..)
+            | Action::MoreTopicsLoaded(..)
+            | Action::TokenChecked(_)
+            | Action::SessionChecked(_)
+            | Action::CheckUnread
+            | Action::UnreadLoaded(_)
+            | Action::CheckIn
+            | Action::CheckedIn(_)
+            | Action::NodeLoaded(..)
+            | Action::TopicRead { .. } => return self.update_route(&Route::Home, action),
+            Action::NotificationsLoaded(..) => {
+                return self.update_route(&Route::Notifications, action)
+            }
+            Action::TopicDetailLoaded(ref url, _)
+            | Action::RepliesLoaded(ref url, ..)
+            | Action::ReplyPosted(ref url, _)
+            | Action::ThankSent(ref url, ..)
+            | Action::FavoriteSet(ref url, ..) => {
+                let route = Route::Detail(url.clone());
+                return self.update_route(&route, action);
+            }
+            // The home page drops an ignored topic from its lists, and the
+            // topic's page goes back from it unless the reader already left.
+            Action::TopicIgnored(url, result) => {
+                self.update_route(
+                    &Route::Home,
+                    Action::TopicIgnored(url.clone(), result.clone()),
+                );
+                let route = Route::Detail(url.clone());
+                let next = self.update_route(&route, Action::TopicIgnored(url, result));
+                return next
+                    .filter(|next| *next != Action::GoBack || *self.router.route() == route);
+            }
+            // Both the nodes page and the node picker keep the list.
+            Action::NodesLoaded(result) => {
+                self.update_route(&Route::NewTopic, Action::NodesLoaded(result.clone()));
+                return self.update_route(&Route::Nodes, Action::NodesLoaded(result));
+            }
+            Action::TopicPosted(_) => return self.update_route(&Route::NewTopic, action),
+            Action::MemberLoaded(ref name, _) => {
+                let route = Route::Member(name.clone());
+                return self.update_route(&route, action);
+            }
+            Action::AddBookmark(_) | Action::SaveBookmark(..) | Action::SnapshotLoaded(..) => {
+                return self.update_route(&Route::Bookmarks, action)
+            }
+            // Bookmarks are added from the home and detail pages, which report back.
+            Action::BookmarkSaved(result) => {
+                self.update_route(&Route::Home, Action::BookmarkSaved(result.clone()));
+                if !matches!(self.router.route(), Route::Detail(_)) {
+                    return None;
+                }
+                return self.router.page().update(Action::BookmarkSaved(result));
+            }
+            Action::TopicVisited { .. } | Action::RestoreSession => {
+                return self.update_route(&Route::History, action)
+            }
+            // The topic left open goes to the history.
+            Action::Quit => {
+                self.leave();
+                return None;
+            }
+            Action::GoNodes => return self.push(Route::Nodes).init(),
+            Action::GoNewTopic => return self.push(Route::NewTopic).init(),
+            Action::GoNotifications => return self.push(Route::Notifications).init(),
+            Action::GoBookmarks => return self.push(Route::Bookmarks).init(),
+            Action::GoHistory => return self.push(Route::History).init(),
+            // A topic just posted takes the place of the emptied form, which
+            // going back then skips.
+            Action::OpenTopic(url) if *self.router.route() == Route::NewTopic => {
+                let route = Route::Detail(url.clone());
+                let page = self.page_for(&route);
+                self.router.replace(route, page);
+                return self.router.page().update(Action::FetchTopicDetail(url));
+            }
+            Action::OpenTopic(url) => {
+                return self
+                    .push(Route::Detail(url.clone()))
+                    .update(Action::FetchTopicDetail(url))
+            }
+            Action::ResumeTopic { ref url, .. } | Action::OpenSnapshot(ref url, _) => {
+                let route = Route::Detail(url.clone());
+                return self.push(route).update(action);
+            }
+            Action::OpenMember(name) => {
+                return self
+                    .push(Route::Member(name.clone()))
+                    .update(Action::FetchMember(name))
+            }
+            Action::GoBack => {
+                if self.router.can_go_back() {
+                    self.leave();
+                    self.router.back();
+                }
+                return None;
+            }
+            Action::GoForward => {
+                if self.router.can_go_forward() {
+                    self.leave();
+                    self.router.forward();
+                }
+                return None;
+            }
+            _ => {}
         }
-        None
+        self.router.page().update(action)
+    }
+
+    /// Shows `route` over the current page, unless it is already on screen,
+    /// and returns its page. Topics and members get a new page each time;
+    /// the other pages come back as they were left.
+    fn push(&mut self, route: Route) -> &mut dyn Page {
+        if *self.router.route() != route {
+            self.leave();
+            let page = self.page_for(&route);
+            self.router.push(route, page);
+        }
+        self.router.page()
+    }
+
+    /// A page of its own for a topic or member; None for the shared pages.
+    fn page_for(&self, route: &Route) -> Option<Box<dyn Page>> {
+        match route {
+            Route::Detail(_) => Some(Box::new(DetailPage::new(
+                self.worker.clone(),
+                self.keymap.clone(),
+                self.scroll_step,
+            ))),
+            Route::Member(_) => Some(Box::new(MemberPage::new(
+                self.worker.clone(),
+                self.keymap.clone(),
+            ))),
+            _ => None,
+        }
+    }
+
+    /// Lets the page on screen know it is being left, and runs what it hands back.
+    fn leave(&mut self) {
+        if let Some(action) = self.router.page().leave() {
+            self.update(action);
+        }
+    }
+
+    fn update_route(&mut self, route: &Route, action: Action) -> Option<Action> {
+        self.router.find(route).and_then(|page| page.update(action))
     }
 }
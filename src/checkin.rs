@@ -0,0 +1,89 @@
+use std::{fs, io, path::PathBuf};
+
+use chrono::{FixedOffset, NaiveDate, Utc};
+
+use crate::{
+    api::{Crawler, FetchError, FetchResult},
+    config::data_dir,
+    i18n::msg,
+    model::CheckIn,
+};
+
+/// The day of the last check-in and the streak V2EX reported then, so that
+/// neither the subcommand nor the TUI submits twice in one day.
+pub struct CheckInRecord {
+    path: PathBuf,
+}
+
+impl CheckInRecord {
+    pub fn load() -> Self {
+        Self {
+            path: data_dir().join("checkin"),
+        }
+    }
+
+    /// The streak recorded for `day`, if the reward was claimed that day.
+    fn streak_on(&self, day: NaiveDate) -> Option<u32> {
+        let content = fs::read_to_string(&self.path).ok()?;
+        let (date, streak) = content.trim().split_once(' ')?;
+        (date.parse::<NaiveDate>().ok()? == day)
+            .then(|| streak.parse().ok())
+            .flatten()
+    }
+
+    fn save(&self, day: NaiveDate, streak: u32) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, format!("{} {}\n", day, streak))
+    }
+}
+
+/// Today as V2EX counts days, which roll over at midnight in Beijing.
+fn today() -> NaiveDate {
+    let beijing = FixedOffset::east_opt(8 * 3600).unwrap();
+    Utc::now().with_timezone(&beijing).date_naive()
+}
+
+/// Claims today's login reward unless the record says it is done already.
+pub fn run(crawler: &Crawler, record: &CheckInRecord) -> FetchResult<CheckIn> {
+    let day = today();
+    if let Some(streak) = record.streak_on(day) {
+        return Ok(CheckIn {
+            claimed: false,
+            coins: None,
+            streak,
+        });
+    }
+    let check_in = crawler.check_in()?;
+    record.save(day, check_in.streak).map_err(|e| {
+        FetchError::new(
+            &record.path.display().to_string(),
+            (msg().check_in_save_failed)(&e.to_string()),
+        )
+    })?;
+    Ok(check_in)
+}
+
+/// A one-line report for the terminal or the home header.
+pub fn describe(check_in: &CheckIn) -> String {
+    match (check_in.claimed, check_in.coins) {
+        (true, Some(coins)) => (msg().checked_in_with_coins)(coins, check_in.streak),
+        (true, None) => (msg().checked_in)(check_in.streak),
+        (false, _) => (msg().already_checked_in)(check_in.streak),
+    }
+}
+
+#[test]
+fn test_check_in_record() {
+    let record = CheckInRecord {
+        path: std::env::temp_dir().join(format!("lazyv2ex-checkin-{}", std::process::id())),
+    };
+    let day = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+    assert_eq!(record.streak_on(day), None);
+
+    record.save(day, 27).unwrap();
+    assert_eq!(record.streak_on(day), Some(27));
+    assert_eq!(record.streak_on(day.succ_opt().unwrap()), None);
+    fs::remove_file(&record.path).unwrap();
+}
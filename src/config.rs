@@ -0,0 +1,256 @@
+use std::{
+    env, fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use color_eyre::{eyre::WrapErr, Result};
+use reqwest::{header::HeaderValue, Url};
+use serde::{de::Error as _, Deserialize, Deserializer};
+
+use crate::{
+    api::{Source, DEFAULT_TIMEOUT, DEFAULT_USER_AGENT, V2EX_URL},
+    i18n::Lang,
+    keymap::Keymap,
+    theme::Theme,
+};
+
+/// Settings read from `~/.config/lazyv2ex/config.toml`. Every field is optional,
+/// and unknown ones are rejected so that a typo does not go unnoticed.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub network: NetworkConfig,
+    pub ui: UiConfig,
+    pub keys: Keymap,
+    pub theme: Theme,
+    pub account: AccountConfig,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NetworkConfig {
+    /// Where to read topics from first; the other source is the fallback.
+    pub source: Source,
+    /// The site every page and feed is fetched from, e.g. a mirror.
+    #[serde(deserialize_with = "base_url")]
+    pub base_url: String,
+    #[serde(deserialize_with = "user_agent")]
+    pub user_agent: String,
+    /// Seconds before a request gives up.
+    #[serde(deserialize_with = "in_range::<_, _, 1, 300>")]
+    pub timeout: u64,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            source: Source::default(),
+            base_url: V2EX_URL.to_string(),
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            timeout: DEFAULT_TIMEOUT.as_secs(),
+        }
+    }
+}
+
+impl NetworkConfig {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UiConfig {
+    /// Language of the interface, `zh-CN` or `en`; the locale decides when unset.
+    pub lang: Option<Lang>,
+    /// Reopen the last topic read, where it was left, instead of starting at home.
+    pub restore_session: bool,
+    /// Lines moved by one scroll in the topic view.
+    #[serde(deserialize_with = "in_range::<_, _, 1, 100>")]
+    pub scroll_step: u16,
+    /// Milliseconds to wait for input before redrawing spinners and results.
+    #[serde(deserialize_with = "in_range::<_, _, 10, 1000>")]
+    pub tick_rate: u64,
+    /// Seconds between checks for unread notifications while logged in.
+    #[serde(deserialize_with = "in_range::<_, _, 30, 3600>")]
+    pub unread_poll_interval: u64,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            lang: None,
+            restore_session: false,
+            scroll_step: 3,
+            tick_rate: 250,
+            unread_poll_interval: 120,
+        }
+    }
+}
+
+impl UiConfig {
+    pub fn tick_rate(&self) -> Duration {
+        Duration::from_millis(self.tick_rate)
+    }
+
+    pub fn unread_poll_interval(&self) -> Duration {
+        Duration::from_secs(self.unread_poll_interval)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AccountConfig {
+    /// Personal access token for API v2, overridden by `V2EX_TOKEN`.
+    pub token: Option<String>,
+    /// Login cookie copied from the browser, e.g. `A2=...; PB3_SESSION=...`.
+    pub cookie: Option<String>,
+    /// Claim the daily login reward whenever the TUI starts with a working login.
+    pub auto_checkin: bool,
+}
+
+impl Config {
+    /// Reads `path`, or the default config file when none is given; only the
+    /// default one may be missing.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let default_path = config_dir().join("config.toml");
+        let path = path.unwrap_or(&default_path);
+        let mut config = match fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content)
+                .wrap_err_with(|| format!("invalid config file {}", path.display()))?,
+            Err(e) if e.kind() == ErrorKind::NotFound && path == default_path => Config::default(),
+            Err(e) => return Err(e).wrap_err_with(|| format!("failed to read {}", path.display())),
+        };
+
+        if let Some(token) = env::var("V2EX_TOKEN").ok().filter(|t| !t.is_empty()) {
+            config.account.token = Some(token);
+        }
+        Ok(config)
+    }
+
+    /// Parses a config file; errors name the line and show it.
+    pub fn parse(content: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(content)
+    }
+}
+
+/// Checks a site url given in the config or on the command line.
+pub fn parse_base_url(value: &str) -> Result<String, String> {
+    match Url::parse(value) {
+        Ok(url) if ["http", "https"].contains(&url.scheme()) && url.has_host() => {
+            Ok(value.trim_end_matches('/').to_string())
+        }
+        _ => Err(format!("`{}` is not an http(s) url", value)),
+    }
+}
+
+fn base_url<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    parse_base_url(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+}
+
+/// Checks a user agent given in the config or on the command line, which
+/// has to be a valid header value.
+pub fn parse_user_agent(value: &str) -> Result<String, String> {
+    match HeaderValue::from_str(value) {
+        Ok(_) => Ok(value.to_string()),
+        Err(_) => Err(format!(
+            "`{}` is not a valid user agent",
+            value.escape_debug()
+        )),
+    }
+}
+
+fn user_agent<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    parse_user_agent(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+}
+
+fn in_range<'de, D, T, const MIN: u64, const MAX: u64>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Into<u64> + Copy,
+{
+    let value = T::deserialize(deserializer)?;
+    if (MIN..=MAX).contains(&value.into()) {
+        Ok(value)
+    } else {
+        Err(D::Error::custom(format!(
+            "must be between {} and {}",
+            MIN, MAX
+        )))
+    }
+}
+
+/// `$XDG_CONFIG_HOME/lazyv2ex`, falling back to `~/.config/lazyv2ex` on every platform.
+pub fn config_dir() -> PathBuf {
+    xdg_dir("XDG_CONFIG_HOME", ".config")
+}
+
+/// `$XDG_DATA_HOME/lazyv2ex`, falling back to `~/.local/share/lazyv2ex`.
+pub fn data_dir() -> PathBuf {
+    xdg_dir("XDG_DATA_HOME", ".local/share")
+}
+
+fn xdg_dir(var: &str, fallback: &str) -> PathBuf {
+    env::var_os(var)
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| dirs::home_dir().map(|home| home.join(fallback)))
+        .unwrap_or_default()
+        .join("lazyv2ex")
+}
+
+#[cfg(test)]
+use crate::{
+    keymap::{Command, Context},
+    theme::Preset,
+};
+#[cfg(test)]
+use ratatui::style::{Color, Modifier, Style};
+
+#[test]
+fn test_parse_config() {
+    let config = Config::parse(
+        "[network]\nsource = \"html\"\nbase_url = \"https://global.v2ex.co/\"\n\n\
+         [ui]\nlang = \"en\"\nscroll_step = 5\n\n[keys]\npreset = \"emacs\"\n\n\
+         [keys.global]\nquit = \"ctrl-q\"\n\n[keys.home]\nnodes = [\"g n\", \"N\"]\n\n[theme]\npreset = \"light\"\nselection = \"#ff8800\"\n",
+    )
+    .unwrap();
+    assert_eq!(config.network.source, Source::Html);
+    assert_eq!(config.network.base_url, "https://global.v2ex.co");
+    assert_eq!(config.ui.scroll_step, 5);
+    assert_eq!(config.ui.lang, Some(Lang::En));
+    assert_eq!(config.ui.tick_rate, 250);
+    let hints = config.keys.hints(
+        Context::Home,
+        &[("退出", &[Command::Quit]), ("节点", &[Command::Nodes])],
+    );
+    assert_eq!(hints, "退出：Ctrl-Q｜节点：gn/N");
+    assert_eq!(
+        config.theme.selection,
+        Style::new()
+            .fg(Color::Rgb(0xff, 0x88, 0x00))
+            .add_modifier(Modifier::REVERSED)
+    );
+    assert_eq!(config.theme.header, Theme::preset(Preset::Light).header);
+
+    let error = Config::parse("[ui]\nrestore_session = true\nscroll_step = 0\n").unwrap_err();
+    let message = error.to_string();
+    assert!(message.contains("line 3"), "{}", message);
+    assert!(message.contains("must be between 1 and 100"), "{}", message);
+
+    let error = Config::parse("[network]\ntimeout = 10\nsorce = \"api\"\n").unwrap_err();
+    assert!(error.to_string().contains("line 3"), "{}", error);
+
+    let error = Config::parse("[network]\nuser_agent = \"bad\\u007fua\"\n").unwrap_err();
+    let message = error.to_string();
+    assert!(message.contains("line 2"), "{}", message);
+    assert!(message.contains("not a valid user agent"), "{}", message);
+
+    let error = Config::parse("[keys.global]\nquit = \"hyper-q\"\n").unwrap_err();
+    assert!(error.to_string().contains("unknown modifier"), "{}", error);
+
+    let error = Config::parse("[keys.nodes]\nthank = \"t\"\n").unwrap_err();
+    assert!(error.to_string().contains("not a command"), "{}", error);
+}
@@ -0,0 +1,45 @@
+//! Hands a draft over to the user's own editor.
+
+use std::{env, fs, io, process::Command};
+
+use crossterm::{
+    execute,
+    terminal::{enable_raw_mode, EnterAlternateScreen},
+};
+use ratatui::DefaultTerminal;
+
+use crate::i18n::msg;
+
+/// Opens `text` in `$VISUAL` or `$EDITOR` (`vi` if neither is set) with the TUI
+/// suspended, and returns what the user saved. Failing to run the editor is not
+/// fatal, only restoring the terminal afterwards is.
+pub fn edit(terminal: &mut DefaultTerminal, text: &str) -> io::Result<Result<String, String>> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let path = env::temp_dir().join(format!("lazyv2ex-{}.md", std::process::id()));
+    if let Err(e) = fs::write(&path, text) {
+        return Ok(Err((msg().temp_file_create_failed)(&e.to_string())));
+    }
+
+    ratatui::restore();
+    // The editor command may carry its own arguments, e.g. `code --wait`.
+    let mut words = editor.split_whitespace();
+    let status = Command::new(words.next().unwrap_or("vi"))
+        .args(words)
+        .arg(&path)
+        .status();
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    terminal.clear()?;
+
+    let result = match status {
+        Ok(status) if status.success() => fs::read_to_string(&path)
+            .map(|text| text.trim_end().to_string())
+            .map_err(|e| (msg().temp_file_read_failed)(&e.to_string())),
+        Ok(status) => Err((msg().editor_exited)(&editor, &status.to_string())),
+        Err(e) => Err((msg().editor_start_failed)(&editor, &e.to_string())),
+    };
+    let _ = fs::remove_file(&path);
+    Ok(result)
+}
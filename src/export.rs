@@ -0,0 +1,63 @@
+//! Writes the topic being read to a file, for `:export`.
+
+use std::{fs, io, path::PathBuf};
+
+use crate::{api::topic_id, model::TopicDetail};
+
+/// What `:export` can write a topic as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Markdown,
+    Json,
+}
+
+impl Format {
+    pub const FORMATS: [Format; 2] = [Format::Markdown, Format::Json];
+
+    /// The name `:export` takes, which is also the file extension.
+    pub fn name(self) -> &'static str {
+        match self {
+            Format::Markdown => "md",
+            Format::Json => "json",
+        }
+    }
+
+    pub fn render(self, detail: &TopicDetail) -> String {
+        match self {
+            Format::Markdown => markdown(detail),
+            Format::Json => serde_json::to_string_pretty(detail).unwrap_or_default(),
+        }
+    }
+}
+
+/// Writes the topic to `path`, or to `<topic id>.<format>` in the working
+/// directory, and returns where it went.
+pub fn write(detail: &TopicDetail, format: Format, path: Option<&str>) -> io::Result<PathBuf> {
+    let path = path.map_or_else(
+        || {
+            let name = topic_id(&detail.link).map_or("topic".to_string(), |id| id.to_string());
+            PathBuf::from(format!("{}.{}", name, format.name()))
+        },
+        PathBuf::from,
+    );
+    fs::write(&path, format.render(detail))?;
+    Ok(path)
+}
+
+/// The topic with every reply loaded so far, one heading per floor.
+fn markdown(detail: &TopicDetail) -> String {
+    let mut text = format!(
+        "# {}\n\n**{}** · {} · <{}>\n\n{}\n",
+        detail.title, detail.author, detail.updated, detail.link, detail.content
+    );
+    for reply in &detail.replies {
+        text.push_str(&format!(
+            "\n---\n\n### #{} **{}** · {}\n\n{}\n",
+            reply.floor(),
+            reply.author,
+            reply.time,
+            reply.content
+        ));
+    }
+    text
+}
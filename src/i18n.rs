@@ -0,0 +1,838 @@
+use std::{env, sync::OnceLock};
+
+use serde::Deserialize;
+
+use crate::keymap::{Category, Command};
+
+/// The language of the interface, picked once at startup.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum, Deserialize)]
+pub enum Lang {
+    #[default]
+    #[value(name = "zh-CN", alias = "zh")]
+    #[serde(rename = "zh-CN", alias = "zh")]
+    ZhCn,
+    #[value(name = "en")]
+    #[serde(rename = "en")]
+    En,
+}
+
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+impl Lang {
+    /// The language asked for by the locale, looking at `LC_ALL`, `LC_MESSAGES`
+    /// and `LANG` in that order like gettext does.
+    pub fn detect() -> Option<Lang> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|var| env::var(var).ok())
+            .find(|locale| !locale.is_empty())
+            .and_then(|locale| Lang::from_locale(&locale))
+    }
+
+    /// Reads a locale such as `en_US.UTF-8` or `zh_CN`; `C` and unknown
+    /// languages give nothing.
+    fn from_locale(locale: &str) -> Option<Lang> {
+        let language = locale.split(['_', '-', '.', '@']).next()?;
+        match language.to_ascii_lowercase().as_str() {
+            "zh" => Some(Lang::ZhCn),
+            "en" => Some(Lang::En),
+            _ => None,
+        }
+    }
+
+    pub fn messages(self) -> &'static Messages {
+        match self {
+            Lang::ZhCn => &ZH_CN,
+            Lang::En => &EN,
+        }
+    }
+}
+
+/// Sets the language for the rest of the run; only the first call counts.
+pub fn init(lang: Lang) {
+    let _ = LANG.set(lang);
+}
+
+/// The messages in the language chosen by `init`, Chinese until then.
+pub fn msg() -> &'static Messages {
+    LANG.get().copied().unwrap_or_default().messages()
+}
+
+/// "1 day" or "3 days".
+fn plural(count: impl Into<i64>, unit: &str) -> String {
+    let count = count.into();
+    if count == 1 {
+        format!("{} {}", count, unit)
+    } else {
+        format!("{} {}s", count, unit)
+    }
+}
+
+/// Labels in the key hints of the footers, error panels and notices.
+pub struct Hints {
+    pub quit: &'static str,
+    pub back: &'static str,
+    pub scroll: &'static str,
+    pub switch: &'static str,
+    pub top: &'static str,
+    pub bottom: &'static str,
+    pub open: &'static str,
+    pub retry: &'static str,
+    pub reload: &'static str,
+    pub member: &'static str,
+    pub load_all: &'static str,
+    pub reply: &'static str,
+    pub open_browser: &'static str,
+    pub filter: &'static str,
+    pub filter_tags: &'static str,
+    pub read_offline: &'static str,
+    pub refresh_snapshot: &'static str,
+    pub edit_tags: &'static str,
+    pub delete: &'static str,
+    pub clear: &'static str,
+    pub resume: &'static str,
+    pub help: &'static str,
+}
+
+/// Every piece of text the interface shows, in one language. Text from V2EX
+/// itself, such as node names and the times on its pages, is shown as is.
+pub struct Messages {
+    pub hint: Hints,
+    /// Goes between a hint label and its keys.
+    pub colon: &'static str,
+    /// Goes between two hints.
+    pub separator: &'static str,
+
+    // Shared by several pages.
+    pub loading: &'static str,
+    pub loading_more: &'static str,
+    pub loading_page: fn(u32) -> String,
+    pub load_failed: fn(&str, &str) -> String,
+    pub load_more_failed: fn(&str) -> String,
+    pub no_more: &'static str,
+    pub request_failed: &'static str,
+    pub reason: &'static str,
+    pub status_code: &'static str,
+    pub address: &'static str,
+    pub confirm: &'static str,
+    pub confirm_hint: &'static str,
+    pub filter_prompt: &'static str,
+    pub filter_hint: &'static str,
+    pub saving: &'static str,
+    pub bookmark_added: fn(&str) -> String,
+    pub bookmark_save_failed: fn(&str) -> String,
+
+    // Table columns.
+    pub topic_columns: [&'static str; 4],
+    pub node_columns: [&'static str; 4],
+    pub notification_columns: [&'static str; 5],
+    pub member_topic_columns: [&'static str; 3],
+    pub member_reply_columns: [&'static str; 3],
+    pub bookmark_columns: [&'static str; 4],
+    pub history_columns: [&'static str; 2],
+
+    // Names from the site.
+    pub tab_tech: &'static str,
+    pub tab_creative: &'static str,
+    pub tab_play: &'static str,
+    pub tab_jobs: &'static str,
+    pub tab_deals: &'static str,
+    pub tab_city: &'static str,
+    pub tab_qna: &'static str,
+    pub tab_hot: &'static str,
+    pub tab_all: &'static str,
+    pub syntax_default: &'static str,
+    pub kind_reply: &'static str,
+    pub kind_mention: &'static str,
+    pub kind_thank: &'static str,
+    pub kind_favorite: &'static str,
+    pub kind_other: &'static str,
+
+    // Home page.
+    pub welcome: &'static str,
+    pub session_expired: &'static str,
+    pub session_check_failed: fn(&str) -> String,
+    pub no_unread: &'static str,
+    pub unread: fn(u32) -> String,
+    pub read_save_failed: fn(&str) -> String,
+    pub check_in_failed: fn(&str) -> String,
+    pub api_quota: &'static str,
+    pub new_replies: fn(u32) -> String,
+
+    // Topic page.
+    pub topic_label: &'static str,
+    pub author_label: &'static str,
+    pub active_label: &'static str,
+    pub marks_label: &'static str,
+    pub content_label: &'static str,
+    pub favorites: fn(u32) -> String,
+    pub favorited: &'static str,
+    pub thanked: &'static str,
+    pub offline_copy: fn(&str) -> String,
+    pub replies_heading: fn(u32, u32) -> String,
+    pub reply_header: fn(&str, &str) -> String,
+    pub reply_thanked: &'static str,
+    pub page_failed: fn(u32, &str, &str) -> String,
+    pub more_pages: fn(u32, &str) -> String,
+    pub sending: &'static str,
+    pub send_failed: fn(&str) -> String,
+    pub multiline: &'static str,
+    pub reply_title: fn(&str) -> String,
+    pub composer_hint: &'static str,
+    pub empty_reply: &'static str,
+    pub reply_already_thanked: &'static str,
+    pub reply_without_id: &'static str,
+    pub thank_reply_prompt: fn(&str, u32) -> String,
+    pub topic_already_thanked: &'static str,
+    pub thank_topic_prompt: fn(&str) -> String,
+    pub favorite_prompt: &'static str,
+    pub unfavorite_prompt: &'static str,
+    pub ignore_prompt: &'static str,
+    pub ignoring: &'static str,
+    pub thanks_sent: &'static str,
+    pub thank_failed: fn(&str) -> String,
+    pub favorite_added: &'static str,
+    pub favorite_removed: &'static str,
+    pub favorite_failed: fn(&str) -> String,
+    pub ignore_failed: fn(&str) -> String,
+
+    // Nodes page and the node picker.
+    pub nodes_title: fn(usize, usize) -> String,
+    pub picker_retry: &'static str,
+
+    // New topic page.
+    pub node_field: &'static str,
+    pub title_field: &'static str,
+    pub body_field: fn(&str) -> String,
+    pub preview_title: fn(&str) -> String,
+    pub node_name: fn(&str) -> String,
+    pub pick_node: &'static str,
+    pub picker_hint: &'static str,
+    pub preview_hint: &'static str,
+    pub editor_hint: &'static str,
+    pub posting: &'static str,
+    pub node_missing: &'static str,
+    pub title_missing: &'static str,
+
+    // Notifications page.
+    pub notifications_title: fn(usize) -> String,
+
+    // Member page.
+    pub member_title: &'static str,
+    pub member_since: fn(u64, &str) -> String,
+    pub website: &'static str,
+    pub location: &'static str,
+    pub recent_topics: fn(usize) -> String,
+    pub recent_replies: fn(usize) -> String,
+
+    // Bookmarks page.
+    pub bookmarks_title: fn(usize, usize) -> String,
+    pub tags_title: &'static str,
+    pub tags_hint: &'static str,
+    pub delete_bookmark_prompt: fn(&str) -> String,
+    pub bookmark_saved: fn(&str) -> String,
+    pub bookmark_deleted: fn(&str) -> String,
+
+    // History page.
+    pub history_title: fn(usize) -> String,
+    pub history_save_failed: fn(&str) -> String,
+    pub delete_history_prompt: fn(&str) -> String,
+    pub clear_history_prompt: &'static str,
+
+    // Daily check-in.
+    pub checked_in_with_coins: fn(u32, u32) -> String,
+    pub checked_in: fn(u32) -> String,
+    pub already_checked_in: fn(u32) -> String,
+    pub check_in_save_failed: fn(&str) -> String,
+
+    // External editor.
+    pub temp_file_create_failed: fn(&str) -> String,
+    pub temp_file_read_failed: fn(&str) -> String,
+    pub editor_exited: fn(&str, &str) -> String,
+    pub editor_start_failed: fn(&str, &str) -> String,
+
+    // Errors found by the client rather than told by the site.
+    pub token_invalid: fn(&str) -> String,
+    pub token_missing: &'static str,
+    pub session_save_failed: fn(&str) -> String,
+    pub session_lost: &'static str,
+    pub unread_missing: &'static str,
+    pub not_a_topic: &'static str,
+    pub topic_missing: &'static str,
+    pub redeem_missing: &'static str,
+    pub redeem_failed: &'static str,
+    pub once_missing: &'static str,
+    pub topic_not_created: &'static str,
+
+    // Help overlay.
+    pub help_title: &'static str,
+    pub help_search: &'static str,
+    pub help_hint: &'static str,
+    pub help_empty: &'static str,
+    pub category: fn(Category) -> &'static str,
+    pub command: fn(Command) -> &'static str,
+
+    // Command line.
+    pub command_unknown: fn(&str) -> String,
+    pub command_usage: fn(&str) -> String,
+    pub command_invalid: fn(&str, &str) -> String,
+    pub command_not_topic: fn(&str) -> String,
+    pub command_history_save_failed: fn(&str) -> String,
+    pub export_no_topic: &'static str,
+    pub exported: fn(&str) -> String,
+    pub export_failed: fn(&str) -> String,
+
+    // Relative times.
+    pub days_ago: fn(i64) -> String,
+    pub hours_minutes_ago: fn(i64, i64) -> String,
+    pub hours_ago: fn(i64) -> String,
+    pub minutes_ago: fn(i64) -> String,
+    pub seconds_ago: fn(i64) -> String,
+}
+
+pub static ZH_CN: Messages = Messages {
+    hint: Hints {
+        quit: "退出",
+        back: "返回",
+        scroll: "滚动",
+        switch: "切换",
+        top: "移到顶部",
+        bottom: "移到底部",
+        open: "查看",
+        retry: "重试",
+        reload: "刷新",
+        member: "成员",
+        load_all: "全部加载",
+        reply: "回复",
+        open_browser: "浏览器打开",
+        filter: "过滤",
+        filter_tags: "标签筛选",
+        read_offline: "离线查看",
+        refresh_snapshot: "刷新副本",
+        edit_tags: "编辑标签",
+        delete: "删除",
+        clear: "清空",
+        resume: "继续阅读",
+        help: "帮助",
+    },
+    colon: "：",
+    separator: "｜",
+
+    loading: "Loading...",
+    loading_more: "加载更多...",
+    loading_page: |page| format!("正在加载第 {} 页...", page),
+    load_failed: |error, hint| format!("加载失败：{}（{}）", error, hint),
+    load_more_failed: |error| format!("加载更多失败：{}", error),
+    no_more: "没有更多了",
+    request_failed: "请求失败",
+    reason: "原因：",
+    status_code: "状态码：",
+    address: "地址：",
+    confirm: "确认",
+    confirm_hint: "确认：y/Enter｜取消：n/Esc",
+    filter_prompt: "过滤：",
+    filter_hint: "完成：Enter/Esc｜删除：Backspace",
+    saving: "正在保存...",
+    bookmark_added: |title| format!("已加入书签：{}", title),
+    bookmark_save_failed: |error| format!("保存书签失败：{}", error),
+
+    topic_columns: ["话题", "楼主", "评论数", "活跃时间"],
+    node_columns: ["节点", "名称", "主题数", "简介"],
+    notification_columns: ["类型", "成员", "主题", "内容", "时间"],
+    member_topic_columns: ["标题", "回复", "更新"],
+    member_reply_columns: ["主题", "内容", "时间"],
+    bookmark_columns: ["话题", "标签", "评论数", "加入时间"],
+    history_columns: ["话题", "访问时间"],
+
+    tab_tech: "技术",
+    tab_creative: "创意",
+    tab_play: "好玩",
+    tab_jobs: "酷工作",
+    tab_deals: "交易",
+    tab_city: "城市",
+    tab_qna: "问与答",
+    tab_hot: "最热",
+    tab_all: "全部",
+    syntax_default: "纯文本",
+    kind_reply: "回复",
+    kind_mention: "提及",
+    kind_thank: "感谢",
+    kind_favorite: "收藏",
+    kind_other: "其他",
+
+    welcome: "欢迎来到 ",
+    session_expired: "登录已失效，请更新 cookie",
+    session_check_failed: |error| format!("登录检查失败：{}", error),
+    no_unread: "没有未读提醒",
+    unread: |count| format!("{} 条未读提醒", count),
+    read_save_failed: |error| format!("已读记录保存失败：{}", error),
+    check_in_failed: |error| format!("签到失败：{}", error),
+    api_quota: "API 剩余",
+    new_replies: |count| format!(" +{} new", count),
+
+    topic_label: "话题：",
+    author_label: "楼主：",
+    active_label: "活跃时间：",
+    marks_label: "标记：",
+    content_label: "内容：",
+    favorites: |count| format!("{} 人收藏", count),
+    favorited: "已收藏",
+    thanked: "已感谢",
+    offline_copy: |hint| format!("离线副本，{}", hint),
+    replies_heading: |loaded, total| format!("评论（第 {}/{} 页）：", loaded, total),
+    reply_header: |author, time| format!(">>> {} 回复于 {}:", author, time),
+    reply_thanked: "（已感谢）",
+    page_failed: |page, error, hint| format!("第 {} 页加载失败：{}（{}）", page, error, hint),
+    more_pages: |count, hint| format!("还有 {} 页，继续向下滚动加载（{}）", count, hint),
+    sending: "正在发送...",
+    send_failed: |error| format!("发送失败：{}", error),
+    multiline: "支持多行，Enter 换行",
+    reply_title: |title| format!("回复：{}", title),
+    composer_hint: "发送：Ctrl-S｜外部编辑器：Ctrl-E｜收起：Esc",
+    empty_reply: "回复内容不能为空",
+    reply_already_thanked: "已经感谢过这条回复了",
+    reply_without_id: "这条回复没有编号，无法感谢",
+    thank_reply_prompt: |author, floor| {
+        format!(
+            "感谢 @{} 的 #{} 楼回复？这会花费 10 个铜币。",
+            author, floor
+        )
+    },
+    topic_already_thanked: "已经感谢过这个主题了",
+    thank_topic_prompt: |author| format!("感谢 @{} 发布的这个主题？这会花费 10 个铜币。", author),
+    favorite_prompt: "收藏这个主题？",
+    unfavorite_prompt: "取消收藏这个主题？",
+    ignore_prompt: "忽略这个主题？之后它不会再出现在列表里。",
+    ignoring: "正在忽略...",
+    thanks_sent: "感谢已发送",
+    thank_failed: |error| format!("感谢失败：{}", error),
+    favorite_added: "已加入收藏",
+    favorite_removed: "已取消收藏",
+    favorite_failed: |error| format!("收藏失败：{}", error),
+    ignore_failed: |error| format!("忽略失败：{}", error),
+
+    nodes_title: |shown, total| format!("节点 {}/{}", shown, total),
+    picker_retry: "重试：Esc 后再按 Enter",
+
+    node_field: "节点",
+    title_field: "标题",
+    body_field: |syntax| format!("正文（{}）", syntax),
+    preview_title: |syntax| format!("预览（{}）", syntax),
+    node_name: |name| format!("（{}）", name),
+    pick_node: "按 Enter 选择节点",
+    picker_hint: "选择：Enter｜过滤：直接输入｜滚动：↑↓｜取消：Esc",
+    preview_hint: "发布：Ctrl-S｜滚动：↑↓｜返回编辑：Esc/Ctrl-P",
+    editor_hint: "切换：Tab｜格式：Ctrl-T｜外部编辑器：Ctrl-E｜预览：Ctrl-P/Ctrl-S｜返回：Esc",
+    posting: "正在发布...",
+    node_missing: "请先选择节点",
+    title_missing: "标题不能为空",
+
+    notifications_title: |count| format!("提醒 {}", count),
+
+    member_title: "会员",
+    member_since: |id, created| format!("第 {} 号会员，加入于 {}", id, created),
+    website: "网站",
+    location: "所在地",
+    recent_topics: |count| format!("最近主题 {}", count),
+    recent_replies: |count| format!("最近回复 {}", count),
+
+    bookmarks_title: |shown, total| format!("书签 {}/{}", shown, total),
+    tags_title: "标签",
+    tags_hint: "保存：Enter｜取消：Esc｜多个标签用空格或逗号分隔",
+    delete_bookmark_prompt: |title| format!("删除书签「{}」及其离线副本？", title),
+    bookmark_saved: |title| format!("已保存「{}」", title),
+    bookmark_deleted: |title| format!("已删除「{}」", title),
+
+    history_title: |count| format!("历史 {}", count),
+    history_save_failed: |error| format!("保存历史失败：{}", error),
+    delete_history_prompt: |title| format!("从历史中删除「{}」？", title),
+    clear_history_prompt: "清空全部浏览历史？",
+
+    checked_in_with_coins: |coins, streak| {
+        format!("签到成功，获得 {} 铜币，已连续登录 {} 天", coins, streak)
+    },
+    checked_in: |streak| format!("签到成功，已连续登录 {} 天", streak),
+    already_checked_in: |streak| format!("今天已经签到过了，已连续登录 {} 天", streak),
+    check_in_save_failed: |error| format!("保存签到记录失败：{}", error),
+
+    temp_file_create_failed: |error| format!("无法创建临时文件：{}", error),
+    temp_file_read_failed: |error| format!("无法读取临时文件：{}", error),
+    editor_exited: |editor, status| format!("{} 异常退出：{}", editor, status),
+    editor_start_failed: |editor, error| format!("无法启动编辑器 {}：{}", editor, error),
+
+    token_invalid: |message| format!("访问令牌无效或已过期：{}", message),
+    token_missing: "未配置访问令牌",
+    session_save_failed: |error| format!("保存登录状态失败：{}", error),
+    session_lost: "登录已失效，请重新登录",
+    unread_missing: "页面上没有提醒数，请先登录",
+    not_a_topic: "不是主题链接",
+    topic_missing: "话题不存在",
+    redeem_missing: "页面上没有领取按钮，请先登录",
+    redeem_failed: "领取每日登录奖励失败",
+    once_missing: "页面上没有 once 令牌，请先登录",
+    topic_not_created: "发帖后没有跳转到新主题",
+
+    help_title: "帮助",
+    help_search: "搜索：",
+    help_hint: "搜索：直接输入｜滚动：↑↓/PgUp/PgDn｜关闭：Esc",
+    help_empty: "没有匹配的按键",
+    category: |category| match category {
+        Category::General => "通用",
+        Category::Movement => "移动",
+        Category::Pages => "页面",
+        Category::Actions => "操作",
+    },
+    command: |command| match command {
+        Command::Quit => "退出",
+        Command::Help => "显示按键帮助",
+        Command::Palette => "输入命令",
+        Command::Up => "向上移动",
+        Command::Down => "向下移动",
+        Command::Top => "移到顶部",
+        Command::Bottom => "移到底部",
+        Command::PreviousTab => "上一个标签",
+        Command::NextTab => "下一个标签",
+        Command::Tab1 => "切换到第 1 个标签",
+        Command::Tab2 => "切换到第 2 个标签",
+        Command::Tab3 => "切换到第 3 个标签",
+        Command::Tab4 => "切换到第 4 个标签",
+        Command::Tab5 => "切换到第 5 个标签",
+        Command::Tab6 => "切换到第 6 个标签",
+        Command::Tab7 => "切换到第 7 个标签",
+        Command::Tab8 => "切换到第 8 个标签",
+        Command::Tab9 => "切换到第 9 个标签",
+        Command::Tab10 => "切换到第 10 个标签",
+        Command::Tab11 => "切换到第 11 个标签",
+        Command::Open => "打开选中的一项",
+        Command::Back => "返回",
+        Command::Forward => "前进",
+        Command::Reload => "刷新",
+        Command::OpenBrowser => "在浏览器中打开",
+        Command::Author => "查看作者",
+        Command::Bookmark => "加入书签",
+        Command::Nodes => "节点列表",
+        Command::NewTopic => "发布新主题",
+        Command::Notifications => "提醒",
+        Command::Bookmarks => "书签",
+        Command::History => "浏览历史",
+        Command::Reply => "回复",
+        Command::LoadAll => "加载全部回复",
+        Command::NextReply => "下一条回复",
+        Command::PreviousReply => "上一条回复",
+        Command::Thank => "感谢",
+        Command::Favorite => "收藏或取消收藏",
+        Command::Ignore => "忽略主题",
+        Command::Filter => "过滤",
+        Command::EditTags => "编辑标签",
+        Command::Delete => "删除",
+        Command::Clear => "清空",
+    },
+
+    command_unknown: |name| format!("没有 {} 这个命令", name),
+    command_usage: |usage| format!("用法：:{}", usage),
+    command_invalid: |value, choices| format!("没有 {}，可选：{}", value, choices),
+    command_not_topic: |value| format!("{} 不是主题编号或链接", value),
+    command_history_save_failed: |error| format!("保存命令历史失败：{}", error),
+    export_no_topic: "只能导出正在阅读的主题",
+    exported: |path| format!("已导出到 {}", path),
+    export_failed: |error| format!("导出失败：{}", error),
+
+    days_ago: |days| format!("{}天前", days),
+    hours_minutes_ago: |hours, minutes| format!("{}小时{}分钟前", hours, minutes),
+    hours_ago: |hours| format!("{}小时前", hours),
+    minutes_ago: |minutes| format!("{}分钟前", minutes),
+    seconds_ago: |seconds| format!("{}秒前", seconds),
+};
+
+pub static EN: Messages = Messages {
+    hint: Hints {
+        quit: "Quit",
+        back: "Back",
+        scroll: "Scroll",
+        switch: "Switch",
+        top: "Top",
+        bottom: "Bottom",
+        open: "Open",
+        retry: "Retry",
+        reload: "Reload",
+        member: "Member",
+        load_all: "Load all",
+        reply: "Reply",
+        open_browser: "Browser",
+        filter: "Filter",
+        filter_tags: "Filter by tag",
+        read_offline: "Read offline",
+        refresh_snapshot: "Refresh copy",
+        edit_tags: "Edit tags",
+        delete: "Delete",
+        clear: "Clear",
+        resume: "Resume",
+        help: "Help",
+    },
+    colon: ": ",
+    separator: " | ",
+
+    loading: "Loading...",
+    loading_more: "Loading more...",
+    loading_page: |page| format!("Loading page {}...", page),
+    load_failed: |error, hint| format!("Failed to load: {} ({})", error, hint),
+    load_more_failed: |error| format!("Failed to load more: {}", error),
+    no_more: "No more",
+    request_failed: "Request failed",
+    reason: "Reason: ",
+    status_code: "Status: ",
+    address: "URL: ",
+    confirm: "Confirm",
+    confirm_hint: "Confirm: y/Enter | Cancel: n/Esc",
+    filter_prompt: "Filter: ",
+    filter_hint: "Done: Enter/Esc | Delete: Backspace",
+    saving: "Saving...",
+    bookmark_added: |title| format!("Bookmarked: {}", title),
+    bookmark_save_failed: |error| format!("Failed to save the bookmark: {}", error),
+
+    topic_columns: ["Topic", "Author", "Replies", "Active"],
+    node_columns: ["Node", "Name", "Topics", "About"],
+    notification_columns: ["Type", "Member", "Topic", "Content", "Time"],
+    member_topic_columns: ["Title", "Replies", "Updated"],
+    member_reply_columns: ["Topic", "Content", "Time"],
+    bookmark_columns: ["Topic", "Tags", "Replies", "Added"],
+    history_columns: ["Topic", "Visited"],
+
+    tab_tech: "Tech",
+    tab_creative: "Creative",
+    tab_play: "Play",
+    tab_jobs: "Jobs",
+    tab_deals: "Deals",
+    tab_city: "City",
+    tab_qna: "Q&A",
+    tab_hot: "Hot",
+    tab_all: "All",
+    syntax_default: "Plain text",
+    kind_reply: "Reply",
+    kind_mention: "Mention",
+    kind_thank: "Thanks",
+    kind_favorite: "Favorite",
+    kind_other: "Other",
+
+    welcome: "Welcome to ",
+    session_expired: "Login expired, please update the cookie",
+    session_check_failed: |error| format!("Login check failed: {}", error),
+    no_unread: "No unread notifications",
+    unread: |count| plural(count, "unread notification"),
+    read_save_failed: |error| format!("Failed to save read topics: {}", error),
+    check_in_failed: |error| format!("Check-in failed: {}", error),
+    api_quota: "API quota",
+    new_replies: |count| format!(" +{} new", count),
+
+    topic_label: "Topic: ",
+    author_label: "Author: ",
+    active_label: "Active: ",
+    marks_label: "Marks: ",
+    content_label: "Content: ",
+    favorites: |count| plural(count, "favorite"),
+    favorited: "Favorited",
+    thanked: "Thanked",
+    offline_copy: |hint| format!("Offline copy, {}", hint),
+    replies_heading: |loaded, total| format!("Replies (page {}/{}):", loaded, total),
+    reply_header: |author, time| format!(">>> {} replied {}:", author, time),
+    reply_thanked: " (thanked)",
+    page_failed: |page, error, hint| format!("Failed to load page {}: {} ({})", page, error, hint),
+    more_pages: |count, hint| {
+        format!(
+            "{} left, keep scrolling to load ({})",
+            plural(count, "page"),
+            hint
+        )
+    },
+    sending: "Sending...",
+    send_failed: |error| format!("Failed to send: {}", error),
+    multiline: "Enter starts a new line",
+    reply_title: |title| format!("Reply: {}", title),
+    composer_hint: "Send: Ctrl-S | External editor: Ctrl-E | Close: Esc",
+    empty_reply: "The reply is empty",
+    reply_already_thanked: "You already thanked this reply",
+    reply_without_id: "This reply has no id and cannot be thanked",
+    thank_reply_prompt: |author, floor| {
+        format!(
+            "Thank @{} for reply #{}? This costs 10 coins.",
+            author, floor
+        )
+    },
+    topic_already_thanked: "You already thanked this topic",
+    thank_topic_prompt: |author| format!("Thank @{} for this topic? This costs 10 coins.", author),
+    favorite_prompt: "Add this topic to favorites?",
+    unfavorite_prompt: "Remove this topic from favorites?",
+    ignore_prompt: "Ignore this topic? It will no longer show up in lists.",
+    ignoring: "Ignoring...",
+    thanks_sent: "Thanks sent",
+    thank_failed: |error| format!("Failed to thank: {}", error),
+    favorite_added: "Added to favorites",
+    favorite_removed: "Removed from favorites",
+    favorite_failed: |error| format!("Failed to favorite: {}", error),
+    ignore_failed: |error| format!("Failed to ignore: {}", error),
+
+    nodes_title: |shown, total| format!("Nodes {}/{}", shown, total),
+    picker_retry: "Retry: Esc, then Enter",
+
+    node_field: "Node",
+    title_field: "Title",
+    body_field: |syntax| format!("Body ({})", syntax),
+    preview_title: |syntax| format!("Preview ({})", syntax),
+    node_name: |name| format!(" ({})", name),
+    pick_node: "Press Enter to pick a node",
+    picker_hint: "Pick: Enter | Filter: type | Scroll: ↑↓ | Cancel: Esc",
+    preview_hint: "Post: Ctrl-S | Scroll: ↑↓ | Edit: Esc/Ctrl-P",
+    editor_hint: "Next field: Tab | Format: Ctrl-T | External editor: Ctrl-E | Preview: Ctrl-P/Ctrl-S | Back: Esc",
+    posting: "Posting...",
+    node_missing: "Pick a node first",
+    title_missing: "The title is empty",
+
+    notifications_title: |count| format!("Notifications {}", count),
+
+    member_title: "Member",
+    member_since: |id, created| format!("Member #{}, joined {}", id, created),
+    website: "Website",
+    location: "Location",
+    recent_topics: |count| format!("Recent topics {}", count),
+    recent_replies: |count| format!("Recent replies {}", count),
+
+    bookmarks_title: |shown, total| format!("Bookmarks {}/{}", shown, total),
+    tags_title: "Tags",
+    tags_hint: "Save: Enter | Cancel: Esc | Separate tags with spaces or commas",
+    delete_bookmark_prompt: |title| format!("Delete the bookmark \"{}\" and its offline copy?", title),
+    bookmark_saved: |title| format!("Saved \"{}\"", title),
+    bookmark_deleted: |title| format!("Deleted \"{}\"", title),
+
+    history_title: |count| format!("History {}", count),
+    history_save_failed: |error| format!("Failed to save the history: {}", error),
+    delete_history_prompt: |title| format!("Remove \"{}\" from the history?", title),
+    clear_history_prompt: "Clear the whole browsing history?",
+
+    checked_in_with_coins: |coins, streak| {
+        format!(
+            "Checked in, got {}, {} in a row",
+            plural(coins, "coin"),
+            plural(streak, "day")
+        )
+    },
+    checked_in: |streak| format!("Checked in, {} in a row", plural(streak, "day")),
+    already_checked_in: |streak| {
+        format!(
+            "Already checked in today, {} in a row",
+            plural(streak, "day")
+        )
+    },
+    check_in_save_failed: |error| format!("Failed to save the check-in record: {}", error),
+
+    temp_file_create_failed: |error| format!("Cannot create the temporary file: {}", error),
+    temp_file_read_failed: |error| format!("Cannot read the temporary file: {}", error),
+    editor_exited: |editor, status| format!("{} failed: {}", editor, status),
+    editor_start_failed: |editor, error| format!("Cannot start the editor {}: {}", editor, error),
+
+    token_invalid: |message| format!("The access token is invalid or expired: {}", message),
+    token_missing: "No access token configured",
+    session_save_failed: |error| format!("Failed to save the login: {}", error),
+    session_lost: "The login has expired, please log in again",
+    unread_missing: "No notification count on the page, please log in first",
+    not_a_topic: "Not a topic link",
+    topic_missing: "The topic does not exist",
+    redeem_missing: "No reward button on the page, please log in first",
+    redeem_failed: "Failed to claim the daily login reward",
+    once_missing: "No once token on the page, please log in first",
+    topic_not_created: "Posting did not lead to the new topic",
+
+    help_title: "Help",
+    help_search: "Search: ",
+    help_hint: "Search: type | Scroll: ↑↓/PgUp/PgDn | Close: Esc",
+    help_empty: "No matching keys",
+    category: |category| match category {
+        Category::General => "General",
+        Category::Movement => "Movement",
+        Category::Pages => "Pages",
+        Category::Actions => "Actions",
+    },
+    command: |command| match command {
+        Command::Quit => "Quit",
+        Command::Help => "Show this help",
+        Command::Palette => "Enter a command",
+        Command::Up => "Move up",
+        Command::Down => "Move down",
+        Command::Top => "Go to the top",
+        Command::Bottom => "Go to the bottom",
+        Command::PreviousTab => "Previous tab",
+        Command::NextTab => "Next tab",
+        Command::Tab1 => "Go to the 1st tab",
+        Command::Tab2 => "Go to the 2nd tab",
+        Command::Tab3 => "Go to the 3rd tab",
+        Command::Tab4 => "Go to the 4th tab",
+        Command::Tab5 => "Go to the 5th tab",
+        Command::Tab6 => "Go to the 6th tab",
+        Command::Tab7 => "Go to the 7th tab",
+        Command::Tab8 => "Go to the 8th tab",
+        Command::Tab9 => "Go to the 9th tab",
+        Command::Tab10 => "Go to the 10th tab",
+        Command::Tab11 => "Go to the 11th tab",
+        Command::Open => "Open the selection",
+        Command::Back => "Go back",
+        Command::Forward => "Go forward",
+        Command::Reload => "Reload",
+        Command::OpenBrowser => "Open in the browser",
+        Command::Author => "Show the author",
+        Command::Bookmark => "Bookmark the topic",
+        Command::Nodes => "Browse nodes",
+        Command::NewTopic => "Post a new topic",
+        Command::Notifications => "Notifications",
+        Command::Bookmarks => "Bookmarks",
+        Command::History => "Browsing history",
+        Command::Reply => "Reply",
+        Command::LoadAll => "Load all replies",
+        Command::NextReply => "Next reply",
+        Command::PreviousReply => "Previous reply",
+        Command::Thank => "Thank",
+        Command::Favorite => "Toggle favorite",
+        Command::Ignore => "Ignore the topic",
+        Command::Filter => "Filter",
+        Command::EditTags => "Edit tags",
+        Command::Delete => "Delete",
+        Command::Clear => "Clear all",
+    },
+
+    command_unknown: |name| format!("Not a command: {}", name),
+    command_usage: |usage| format!("Usage: :{}", usage),
+    command_invalid: |value, choices| format!("No {}, try one of: {}", value, choices),
+    command_not_topic: |value| format!("{} is not a topic id or link", value),
+    command_history_save_failed: |error| {
+        format!("Failed to save the command history: {}", error)
+    },
+    export_no_topic: "Only an open topic can be exported",
+    exported: |path| format!("Exported to {}", path),
+    export_failed: |error| format!("Failed to export: {}", error),
+
+    days_ago: |days| format!("{} ago", plural(days, "day")),
+    hours_minutes_ago: |hours, minutes| {
+        format!(
+            "{} {} ago",
+            plural(hours, "hour"),
+            plural(minutes, "minute")
+        )
+    },
+    hours_ago: |hours| format!("{} ago", plural(hours, "hour")),
+    minutes_ago: |minutes| format!("{} ago", plural(minutes, "minute")),
+    seconds_ago: |seconds| format!("{} ago", plural(seconds, "second")),
+};
+
+#[test]
+fn test_lang() {
+    assert_eq!(Lang::from_locale("en_US.UTF-8"), Some(Lang::En));
+    assert_eq!(Lang::from_locale("zh_CN.UTF-8"), Some(Lang::ZhCn));
+    assert_eq!(Lang::from_locale("zh-TW"), Some(Lang::ZhCn));
+    assert_eq!(Lang::from_locale("C"), None);
+    assert_eq!(Lang::from_locale("fr_FR"), None);
+
+    assert_eq!((EN.unread)(1), "1 unread notification");
+    assert_eq!((EN.unread)(3), "3 unread notifications");
+    assert_eq!(
+        (EN.more_pages)(1, "a"),
+        "1 page left, keep scrolling to load (a)"
+    );
+}
@@ -0,0 +1,761 @@
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    Deserialize, Deserializer,
+};
+
+use crate::i18n::msg;
+
+/// A key with its modifiers, written like `q`, `ctrl-c`, `shift-tab` or `f5`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Key {
+    /// Shift is part of the character for printable keys, so it is folded into
+    /// the character and dropped from the modifiers.
+    pub fn new(code: KeyCode, mut modifiers: KeyModifiers) -> Self {
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            KeyCode::BackTab => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            code => code,
+        };
+        Self { code, modifiers }
+    }
+
+    fn is_char(&self) -> bool {
+        matches!(self.code, KeyCode::Char(c) if c != ' ') && self.modifiers.is_empty()
+    }
+}
+
+impl From<KeyEvent> for Key {
+    fn from(event: KeyEvent) -> Self {
+        Self::new(event.code, event.modifiers)
+    }
+}
+
+impl FromStr for Key {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = s;
+        // A lone "-" is the minus key, not a separator.
+        while let Some((modifier, tail)) = rest.split_once('-').filter(|(_, tail)| !tail.is_empty())
+        {
+            modifiers |= match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "c" => KeyModifiers::CONTROL,
+                "alt" | "m" => KeyModifiers::ALT,
+                "shift" | "s" => KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier `{}` in key `{}`", modifier, s)),
+            };
+            rest = tail;
+        }
+        let code = match rest.to_ascii_lowercase().as_str() {
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "space" => KeyCode::Char(' '),
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            name if name.len() > 1 && name.starts_with('f') => match name[1..].parse() {
+                Ok(n @ 1..=12) => KeyCode::F(n),
+                _ => return Err(format!("unknown key `{}`", s)),
+            },
+            _ => {
+                let mut chars = rest.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => return Err(format!("unknown key `{}`", s)),
+                }
+            }
+        };
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl-"),
+            (KeyModifiers::ALT, "Alt-"),
+            (KeyModifiers::SHIFT, "Shift-"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                f.write_str(name)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char(c) if self.modifiers.contains(KeyModifiers::CONTROL) => {
+                write!(f, "{}", c.to_ascii_uppercase())
+            }
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Up => f.write_str("↑"),
+            KeyCode::Down => f.write_str("↓"),
+            KeyCode::Left => f.write_str("←"),
+            KeyCode::Right => f.write_str("→"),
+            KeyCode::Enter => f.write_str("Enter"),
+            KeyCode::Backspace => f.write_str("Backspace"),
+            KeyCode::BackTab => f.write_str("Shift-Tab"),
+            KeyCode::Delete => f.write_str("Del"),
+            KeyCode::PageUp => f.write_str("PgUp"),
+            KeyCode::PageDown => f.write_str("PgDn"),
+            code => write!(f, "{}", code),
+        }
+    }
+}
+
+/// One or more keys pressed one after another, written like `g g` or
+/// `ctrl-x ctrl-c`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Sequence(Vec<Key>);
+
+impl FromStr for Sequence {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let keys = s
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<Vec<Key>, _>>()?;
+        if keys.is_empty() {
+            return Err("empty key sequence".to_string());
+        }
+        Ok(Self(keys))
+    }
+}
+
+impl TryFrom<String> for Sequence {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl fmt::Display for Sequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // `gg` reads better than `g g`, but `Ctrl-X Ctrl-C` needs the space.
+        let separator = if self.0.iter().all(Key::is_char) {
+            ""
+        } else {
+            " "
+        };
+        for (index, key) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(separator)?;
+            }
+            write!(f, "{}", key)?;
+        }
+        Ok(())
+    }
+}
+
+/// The keys bound to one command in the config: a single sequence, or a list
+/// of them where an empty list unbinds the command.
+#[derive(Debug, Clone, Default)]
+pub struct Sequences(Vec<Sequence>);
+
+impl<'de> Deserialize<'de> for Sequences {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SequencesVisitor;
+
+        impl<'de> Visitor<'de> for SequencesVisitor {
+            type Value = Sequences;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a key sequence or a list of them")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Sequences, E> {
+                value.parse().map(|s| Sequences(vec![s])).map_err(E::custom)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Sequences, A::Error> {
+                let mut sequences = vec![];
+                while let Some(sequence) = seq.next_element()? {
+                    sequences.push(sequence);
+                }
+                Ok(Sequences(sequences))
+            }
+        }
+
+        deserializer.deserialize_any(SequencesVisitor)
+    }
+}
+
+/// Everything a key can be bound to. Pages turn a command into an action
+/// using what is selected on them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Command {
+    Quit,
+    Help,
+    Palette,
+    Up,
+    Down,
+    Top,
+    Bottom,
+    PreviousTab,
+    NextTab,
+    // The tabs in the site's order. Digits only go to ten, so the last one,
+    // R2, has no key until one is configured.
+    Tab1,
+    Tab2,
+    Tab3,
+    Tab4,
+    Tab5,
+    Tab6,
+    Tab7,
+    Tab8,
+    Tab9,
+    Tab10,
+    Tab11,
+    Open,
+    Back,
+    Forward,
+    Reload,
+    OpenBrowser,
+    Author,
+    Bookmark,
+    Nodes,
+    NewTopic,
+    Notifications,
+    Bookmarks,
+    History,
+    Reply,
+    LoadAll,
+    NextReply,
+    PreviousReply,
+    Thank,
+    Favorite,
+    Ignore,
+    Filter,
+    EditTags,
+    Delete,
+    Clear,
+}
+
+/// Written the way the config names it, e.g. `previous_tab`.
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, c) in format!("{:?}", self).chars().enumerate() {
+            if c.is_ascii_uppercase() && index > 0 {
+                f.write_str("_")?;
+            }
+            write!(f, "{}", c.to_ascii_lowercase())?;
+        }
+        Ok(())
+    }
+}
+
+impl Command {
+    /// The group the help lists the command under.
+    pub fn category(self) -> Category {
+        use Command::*;
+        match self {
+            Quit | Help | Palette | Back | Forward | Reload | OpenBrowser => Category::General,
+            Up | Down | Top | Bottom | PreviousTab | NextTab | Tab1 | Tab2 | Tab3 | Tab4 | Tab5
+            | Tab6 | Tab7 | Tab8 | Tab9 | Tab10 | Tab11 | NextReply | PreviousReply | LoadAll => {
+                Category::Movement
+            }
+            Open | Author | Nodes | NewTopic | Notifications | Bookmarks | History => {
+                Category::Pages
+            }
+            Bookmark | Reply | Thank | Favorite | Ignore | Filter | EditTags | Delete | Clear => {
+                Category::Actions
+            }
+        }
+    }
+
+    /// Which of the tabs a `tabN` command goes to, counting from 0.
+    pub fn tab_index(self) -> Option<usize> {
+        use Command::*;
+        [
+            Tab1, Tab2, Tab3, Tab4, Tab5, Tab6, Tab7, Tab8, Tab9, Tab10, Tab11,
+        ]
+        .iter()
+        .position(|command| *command == self)
+    }
+}
+
+/// How the help groups commands, in the order it lists them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Category {
+    General,
+    Movement,
+    Pages,
+    Actions,
+}
+
+/// Where a binding applies. Page bindings win over global ones, and a command
+/// bound on a page no longer answers to its global keys there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Context {
+    Global,
+    Home,
+    Detail,
+    Nodes,
+    Notifications,
+    Member,
+    Bookmarks,
+    History,
+}
+
+impl Context {
+    pub fn name(self) -> &'static str {
+        match self {
+            Context::Global => "global",
+            Context::Home => "home",
+            Context::Detail => "detail",
+            Context::Nodes => "nodes",
+            Context::Notifications => "notifications",
+            Context::Member => "member",
+            Context::Bookmarks => "bookmarks",
+            Context::History => "history",
+        }
+    }
+
+    /// The commands that mean something here; every page also takes the global ones.
+    pub fn commands(self) -> &'static [Command] {
+        use Command::*;
+        match self {
+            Context::Global => &[
+                Quit,
+                Help,
+                Palette,
+                Up,
+                Down,
+                Top,
+                Bottom,
+                PreviousTab,
+                NextTab,
+                Open,
+                Back,
+                Forward,
+            ],
+            Context::Home => &[
+                Tab1,
+                Tab2,
+                Tab3,
+                Tab4,
+                Tab5,
+                Tab6,
+                Tab7,
+                Tab8,
+                Tab9,
+                Tab10,
+                Tab11,
+                Reload,
+                Nodes,
+                NewTopic,
+                Notifications,
+                Bookmarks,
+                History,
+                Bookmark,
+                Author,
+            ],
+            Context::Detail => &[
+                Reload,
+                Reply,
+                LoadAll,
+                NextReply,
+                PreviousReply,
+                Thank,
+                Favorite,
+                Ignore,
+                Bookmark,
+                Author,
+                OpenBrowser,
+            ],
+            Context::Nodes => &[Reload, Filter],
+            Context::Notifications => &[Reload, Author, OpenBrowser],
+            Context::Member => &[Reload, OpenBrowser],
+            Context::Bookmarks => &[Reload, EditTags, Delete, OpenBrowser],
+            Context::History => &[Delete, Clear, OpenBrowser],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Preset {
+    /// hjkl to move, `gg`/`G` for the ends and `q` to quit.
+    #[default]
+    Vim,
+    /// `C-n`/`C-p` to move, `M-<`/`M->` for the ends and `C-x C-c` to quit.
+    Emacs,
+}
+
+type Table = &'static [(Context, Command, &'static [&'static str])];
+
+const VIM: Table = &[
+    (Context::Global, Command::Quit, &["q"]),
+    (Context::Global, Command::Help, &["?"]),
+    (Context::Global, Command::Palette, &[":"]),
+    (Context::Global, Command::Up, &["up", "k"]),
+    (Context::Global, Command::Down, &["down", "j"]),
+    (Context::Global, Command::Top, &["g g", "t"]),
+    (Context::Global, Command::Bottom, &["G", "b"]),
+    (Context::Global, Command::PreviousTab, &["left", "h"]),
+    (Context::Global, Command::NextTab, &["right", "l"]),
+    (Context::Global, Command::Open, &["enter"]),
+    (Context::Global, Command::Back, &["esc", "backspace"]),
+    (Context::Global, Command::Forward, &["alt-right"]),
+];
+
+const EMACS: Table = &[
+    (Context::Global, Command::Quit, &["ctrl-x ctrl-c"]),
+    (Context::Global, Command::Help, &["?", "f1"]),
+    (Context::Global, Command::Palette, &[":", "alt-x"]),
+    (Context::Global, Command::Up, &["up", "ctrl-p"]),
+    (Context::Global, Command::Down, &["down", "ctrl-n"]),
+    (Context::Global, Command::Top, &["home", "alt-<"]),
+    (Context::Global, Command::Bottom, &["end", "alt->"]),
+    (Context::Global, Command::PreviousTab, &["left", "ctrl-b"]),
+    (Context::Global, Command::NextTab, &["right", "ctrl-f"]),
+    (Context::Global, Command::Open, &["enter"]),
+    (Context::Global, Command::Back, &["esc", "ctrl-g"]),
+    (Context::Global, Command::Forward, &["alt-right"]),
+];
+
+/// Page keys shared by both presets, which only differ in how to move around.
+const PAGES: Table = &[
+    (Context::Home, Command::Tab1, &["1"]),
+    (Context::Home, Command::Tab2, &["2"]),
+    (Context::Home, Command::Tab3, &["3"]),
+    (Context::Home, Command::Tab4, &["4"]),
+    (Context::Home, Command::Tab5, &["5"]),
+    (Context::Home, Command::Tab6, &["6"]),
+    (Context::Home, Command::Tab7, &["7"]),
+    (Context::Home, Command::Tab8, &["8"]),
+    (Context::Home, Command::Tab9, &["9"]),
+    (Context::Home, Command::Tab10, &["0"]),
+    (Context::Home, Command::Reload, &["r"]),
+    (Context::Home, Command::Nodes, &["n"]),
+    (Context::Home, Command::NewTopic, &["w"]),
+    (Context::Home, Command::Notifications, &["m"]),
+    (Context::Home, Command::Bookmarks, &["B"]),
+    (Context::Home, Command::History, &["H"]),
+    (Context::Home, Command::Bookmark, &["s"]),
+    (Context::Home, Command::Author, &["u"]),
+    (Context::Detail, Command::Reload, &["R"]),
+    (Context::Detail, Command::Reply, &["r"]),
+    (Context::Detail, Command::LoadAll, &["a"]),
+    (Context::Detail, Command::NextReply, &["n"]),
+    (Context::Detail, Command::PreviousReply, &["p"]),
+    (Context::Detail, Command::Thank, &["T"]),
+    (Context::Detail, Command::Favorite, &["f"]),
+    (Context::Detail, Command::Ignore, &["i"]),
+    (Context::Detail, Command::Bookmark, &["s"]),
+    (Context::Detail, Command::Author, &["u"]),
+    (Context::Detail, Command::OpenBrowser, &["o"]),
+    (Context::Nodes, Command::Reload, &["r"]),
+    (Context::Nodes, Command::Filter, &["/"]),
+    (Context::Notifications, Command::Reload, &["r"]),
+    (Context::Notifications, Command::Author, &["u"]),
+    (Context::Notifications, Command::OpenBrowser, &["o"]),
+    (Context::Member, Command::Reload, &["r"]),
+    (Context::Member, Command::OpenBrowser, &["o"]),
+    (Context::Bookmarks, Command::Reload, &["r"]),
+    (Context::Bookmarks, Command::EditTags, &["e"]),
+    (Context::Bookmarks, Command::Delete, &["d"]),
+    (Context::Bookmarks, Command::OpenBrowser, &["o"]),
+    (Context::History, Command::Delete, &["d"]),
+    (Context::History, Command::Clear, &["c"]),
+    (Context::History, Command::OpenBrowser, &["o"]),
+];
+
+#[derive(Debug, Clone)]
+struct Binding {
+    context: Context,
+    command: Command,
+    sequences: Vec<Sequence>,
+}
+
+/// What the keys pressed so far amount to.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Lookup {
+    Command(Command),
+    /// The start of a longer sequence; wait for the next key.
+    Prefix,
+    None,
+}
+
+/// The key bindings of every page: a preset with the config's changes on top.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "KeysConfig")]
+pub struct Keymap {
+    bindings: Vec<Binding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::preset(Preset::default())
+    }
+}
+
+impl Keymap {
+    pub fn preset(preset: Preset) -> Self {
+        let navigation = match preset {
+            Preset::Vim => VIM,
+            Preset::Emacs => EMACS,
+        };
+        let bindings = navigation
+            .iter()
+            .chain(PAGES)
+            .map(|(context, command, keys)| Binding {
+                context: *context,
+                command: *command,
+                sequences: keys
+                    .iter()
+                    .map(|keys| keys.parse().expect("preset keys parse"))
+                    .collect(),
+            })
+            .collect();
+        Self { bindings }
+    }
+
+    /// Binds `command` to exactly `sequences` in `context`, taking those keys
+    /// away from whatever they did there before.
+    pub fn bind(
+        &mut self,
+        context: Context,
+        command: Command,
+        sequences: Sequences,
+    ) -> Result<(), String> {
+        if !Context::Global.commands().contains(&command) && !context.commands().contains(&command)
+        {
+            return Err(format!(
+                "`{}` is not a command of [keys.{}]",
+                command,
+                context.name()
+            ));
+        }
+        let sequences = sequences.0;
+        for binding in self.bindings.iter_mut().filter(|b| b.context == context) {
+            binding.sequences.retain(|s| !sequences.contains(s));
+        }
+        match self
+            .bindings
+            .iter_mut()
+            .find(|b| b.context == context && b.command == command)
+        {
+            Some(binding) => binding.sequences = sequences,
+            None => self.bindings.push(Binding {
+                context,
+                command,
+                sequences,
+            }),
+        }
+        Ok(())
+    }
+
+    /// The bindings that apply in `context`: its own, then the global ones for
+    /// commands it does not rebind.
+    fn active(&self, context: Context) -> impl Iterator<Item = &Binding> {
+        let own = self.bindings.iter().filter(move |b| b.context == context);
+        let global = self.bindings.iter().filter(move |b| {
+            b.context == Context::Global
+                && !self
+                    .bindings
+                    .iter()
+                    .any(|own| own.context == context && own.command == b.command)
+        });
+        own.chain(global)
+    }
+
+    pub fn lookup(&self, context: Context, keys: &[Key]) -> Lookup {
+        let mut prefix = false;
+        for binding in self.active(context) {
+            for sequence in &binding.sequences {
+                if sequence.0 == keys {
+                    return Lookup::Command(binding.command);
+                }
+                prefix |= sequence.0.len() > keys.len() && sequence.0.starts_with(keys);
+            }
+        }
+        if prefix {
+            Lookup::Prefix
+        } else {
+            Lookup::None
+        }
+    }
+
+    pub fn sequences(&self, context: Context, command: Command) -> &[Sequence] {
+        self.active(context)
+            .find(|b| b.command == command)
+            .map_or(&[], |b| &b.sequences)
+    }
+
+    /// Every command that means something in `context` with the keys bound to
+    /// it there, leaving out unbound ones.
+    pub fn bindings(&self, context: Context) -> Vec<(Command, &[Sequence])> {
+        Context::Global
+            .commands()
+            .iter()
+            .chain(context.commands())
+            .map(|command| (*command, self.sequences(context, *command)))
+            .filter(|(_, sequences)| !sequences.is_empty())
+            .collect()
+    }
+
+    /// A footer line such as `退出：q｜滚动：↑↓/kj`, listing the keys bound to
+    /// each labelled group of commands and leaving out groups with none.
+    pub fn hints(&self, context: Context, hints: &[(&str, &[Command])]) -> String {
+        hints
+            .iter()
+            .filter_map(|(label, commands)| {
+                let keys: Vec<Vec<String>> = commands
+                    .iter()
+                    .map(|command| {
+                        self.sequences(context, *command)
+                            .iter()
+                            .map(Sequence::to_string)
+                            .collect()
+                    })
+                    .collect();
+                let rows = keys.iter().map(Vec::len).max().unwrap_or(0);
+                // Pairs like up/down read as `↑↓/kj`: the n-th key of each side by side.
+                let columns: Vec<String> = (0..rows)
+                    .map(|row| {
+                        let column: Vec<&str> = keys
+                            .iter()
+                            .filter_map(|keys| keys.get(row).map(String::as_str))
+                            .collect();
+                        let separator = if column.iter().all(|k| k.chars().count() == 1) {
+                            ""
+                        } else {
+                            " "
+                        };
+                        column.join(separator)
+                    })
+                    .collect();
+                (!columns.is_empty())
+                    .then(|| format!("{}{}{}", label, msg().colon, columns.join("/")))
+            })
+            .collect::<Vec<_>>()
+            .join(msg().separator)
+    }
+}
+
+/// The `[keys]` section: a preset, then per-page tables of `command = keys`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct KeysConfig {
+    preset: Preset,
+    global: BTreeMap<Command, Sequences>,
+    home: BTreeMap<Command, Sequences>,
+    detail: BTreeMap<Command, Sequences>,
+    nodes: BTreeMap<Command, Sequences>,
+    notifications: BTreeMap<Command, Sequences>,
+    member: BTreeMap<Command, Sequences>,
+    bookmarks: BTreeMap<Command, Sequences>,
+    history: BTreeMap<Command, Sequences>,
+}
+
+impl TryFrom<KeysConfig> for Keymap {
+    type Error = String;
+
+    fn try_from(config: KeysConfig) -> Result<Self, Self::Error> {
+        let mut keymap = Keymap::preset(config.preset);
+        for (context, bindings) in [
+            (Context::Global, config.global),
+            (Context::Home, config.home),
+            (Context::Detail, config.detail),
+            (Context::Nodes, config.nodes),
+            (Context::Notifications, config.notifications),
+            (Context::Member, config.member),
+            (Context::Bookmarks, config.bookmarks),
+            (Context::History, config.history),
+        ] {
+            for (command, sequences) in bindings {
+                keymap.bind(context, command, sequences)?;
+            }
+        }
+        Ok(keymap)
+    }
+}
+
+#[test]
+fn test_keymap() {
+    let key = |s: &str| s.parse::<Key>().unwrap();
+    let keymap = Keymap::default();
+    assert_eq!(keymap.lookup(Context::Home, &[key("g")]), Lookup::Prefix);
+    assert_eq!(
+        keymap.lookup(Context::Home, &[key("g"), key("g")]),
+        Lookup::Command(Command::Top)
+    );
+    assert_eq!(
+        keymap.lookup(Context::Detail, &[key("shift-g")]),
+        Lookup::Command(Command::Bottom)
+    );
+    assert_eq!(keymap.lookup(Context::Home, &[key("x")]), Lookup::None);
+    assert_eq!(
+        keymap.lookup(Context::Home, &[key("0")]),
+        Lookup::Command(Command::Tab10)
+    );
+    assert_eq!(Command::Tab10.tab_index(), Some(9));
+    assert!(keymap.sequences(Context::Home, Command::Tab11).is_empty());
+    assert_eq!(keymap.lookup(Context::Detail, &[key("1")]), Lookup::None);
+    assert_eq!(
+        keymap.hints(Context::Home, &[("滚动", &[Command::Up, Command::Down])]),
+        "滚动：↑↓/kj"
+    );
+
+    let mut keymap = Keymap::preset(Preset::Emacs);
+    assert_eq!(
+        keymap.lookup(Context::History, &[key("ctrl-x"), key("ctrl-c")]),
+        Lookup::Command(Command::Quit)
+    );
+    assert_eq!(keymap.lookup(Context::History, &[key("q")]), Lookup::None);
+    assert_eq!(
+        keymap.hints(Context::Home, &[("退出", &[Command::Quit])]),
+        "退出：Ctrl-X Ctrl-C"
+    );
+
+    // Rebinding on a page takes the key from its old command there, and
+    // replaces the global keys of the rebound command on that page only.
+    keymap
+        .bind(
+            Context::Detail,
+            Command::Top,
+            Sequences(vec!["r".parse().unwrap()]),
+        )
+        .unwrap();
+    assert_eq!(
+        keymap.lookup(Context::Detail, &[key("r")]),
+        Lookup::Command(Command::Top)
+    );
+    assert_eq!(keymap.lookup(Context::Detail, &[key("home")]), Lookup::None);
+    assert_eq!(
+        keymap.lookup(Context::Home, &[key("home")]),
+        Lookup::Command(Command::Top)
+    );
+    assert_eq!(keymap.sequences(Context::Detail, Command::Reply), &[]);
+    assert!(keymap
+        .bind(Context::Nodes, Command::Thank, Sequences::default())
+        .is_err());
+}
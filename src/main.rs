@@ -1,57 +1,191 @@
-use std::time::Duration;
-
 use action::Action;
+use api::{session::Session, Crawler, Source};
 use app::App;
-use clap::Parser;
+use checkin::CheckInRecord;
+use clap::{Parser, Subcommand};
 use color_eyre::Result;
+use config::Config;
 use crossterm::event::{self};
-use pages::PageType;
+use i18n::Lang;
+use ratatui::DefaultTerminal;
+use std::path::PathBuf;
 
 mod action;
 mod api;
 mod app;
+mod checkin;
+mod config;
+mod editor;
+mod export;
+mod i18n;
+mod keymap;
 mod model;
 mod pages;
+mod router;
+mod store;
+mod theme;
 mod time;
+mod worker;
 
 /// A command-line client for v2ex
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
-struct Args {}
+struct Args {
+    /// Config file to read instead of ~/.config/lazyv2ex/config.toml
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Where to read topics from first; the other source is the fallback
+    #[arg(long, value_enum)]
+    source: Option<Source>,
+
+    /// Site to fetch pages and feeds from, e.g. a mirror
+    #[arg(long, value_name = "URL", value_parser = config::parse_base_url)]
+    base_url: Option<String>,
+
+    /// User agent sent with every request
+    #[arg(long, value_parser = config::parse_user_agent)]
+    user_agent: Option<String>,
+
+    /// Seconds before a request gives up
+    #[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..=300))]
+    timeout: Option<u64>,
+
+    /// Milliseconds to wait for input before redrawing
+    #[arg(long, value_name = "MS", value_parser = clap::value_parser!(u64).range(10..=1000))]
+    tick_rate: Option<u64>,
+
+    /// Language of the interface; defaults to the config, then the locale
+    #[arg(long, value_enum)]
+    lang: Option<Lang>,
+
+    /// Reopen the last topic read instead of starting at home
+    #[arg(long, overrides_with = "no_restore")]
+    restore: bool,
+
+    /// Start at home even when the config restores the last session
+    #[arg(long)]
+    no_restore: bool,
+
+    /// Run a `:` command after starting, e.g. `--command "tab hot"`; may be repeated
+    #[arg(short = 'c', long = "command", value_name = "COMMAND")]
+    commands: Vec<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Claim today's daily login reward and exit
+    Checkin,
+}
+
+impl Args {
+    /// Flags given on the command line win over the config file.
+    fn apply(&self, config: &mut Config) {
+        if let Some(source) = self.source {
+            config.network.source = source;
+        }
+        if let Some(base_url) = &self.base_url {
+            config.network.base_url = base_url.clone();
+        }
+        if let Some(user_agent) = &self.user_agent {
+            config.network.user_agent = user_agent.clone();
+        }
+        if let Some(timeout) = self.timeout {
+            config.network.timeout = timeout;
+        }
+        if let Some(tick_rate) = self.tick_rate {
+            config.ui.tick_rate = tick_rate;
+        }
+        if let Some(lang) = self.lang {
+            config.ui.lang = Some(lang);
+        }
+        if self.restore {
+            config.ui.restore_session = true;
+        }
+        if self.no_restore {
+            config.ui.restore_session = false;
+        }
+    }
+}
 
 fn main() -> Result<()> {
     color_eyre::install()?;
 
-    let _ = Args::parse();
+    let args = Args::parse();
+    let mut config = Config::load(args.config.as_deref())?;
+    args.apply(&mut config);
+    i18n::init(config.ui.lang.or_else(Lang::detect).unwrap_or_default());
 
-    let mut terminal = ratatui::init();
+    let network = &config.network;
+    let crawler = Crawler::with_client(&network.user_agent, network.timeout())?
+        .with_base_url(&network.base_url)
+        .with_session(Session::load(config.account.cookie.clone()))
+        .with_source(network.source)
+        .with_token(config.account.token.clone());
+    if let Some(Command::Checkin) = args.command {
+        let check_in = checkin::run(&crawler, &CheckInRecord::load())?;
+        println!("{}", checkin::describe(&check_in));
+        return Ok(());
+    }
 
-    let mut app = App::new();
+    let mut terminal = ratatui::init();
+    let mut app = App::new(crawler, &config);
     terminal.draw(|f| app.render(f))?;
-    let init_action = app.switch_page(PageType::Home);
+    let init_action = app.init();
     app.update(init_action.unwrap());
+    if config.account.auto_checkin {
+        app.update(Action::CheckIn);
+    }
+    if config.ui.restore_session {
+        dispatch(&mut terminal, &mut app, Action::RestoreSession)?;
+    }
+    for command in &args.commands {
+        if let Some(action) = app.run_command(command) {
+            dispatch(&mut terminal, &mut app, action)?;
+        }
+    }
 
     loop {
         terminal.draw(|f| app.render(f))?;
 
-        if event::poll(Duration::from_millis(250))? {
+        let action = if event::poll(config.ui.tick_rate())? {
             let event = event::read()?;
-            let action = app.handle_event(event);
-            if let Some(Action::Quit) = action {
-                break;
-            }
-            if action.is_none() {
-                continue;
-            }
-
-            let mut current_action = action.unwrap();
+            app.handle_event(event)
+        } else {
+            Some(Action::Tick)
+        };
+        if let Some(Action::Quit) = action {
+            app.update(Action::Quit);
+            break;
+        }
+        if let Some(action) = action {
+            dispatch(&mut terminal, &mut app, action)?;
+        }
 
-            while let Some(next_action) = app.update(current_action) {
-                terminal.draw(|f| app.render(f))?;
-                current_action = next_action;
-            }
+        while let Some(action) = app.try_recv() {
+            dispatch(&mut terminal, &mut app, action)?;
         }
     }
     ratatui::restore();
     Ok(())
 }
+
+/// Runs an action and every follow-up action it produces, redrawing in between.
+/// Opening an external editor needs the terminal itself, so it is handled here.
+fn dispatch(terminal: &mut DefaultTerminal, app: &mut App, action: Action) -> Result<()> {
+    let mut current_action = action;
+    loop {
+        let next_action = match current_action {
+            Action::OpenEditor(text) => Some(Action::EditorClosed(editor::edit(terminal, &text)?)),
+            action => app.update(action),
+        };
+        let Some(next_action) = next_action else {
+            return Ok(());
+        };
+        terminal.draw(|f| app.render(f))?;
+        current_action = next_action;
+    }
+}
@@ -1,3 +1,7 @@
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::msg;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Topic {
     pub title: String,
@@ -10,6 +14,11 @@ pub struct Topic {
 }
 
 impl Topic {
+    /// The reply count, which feeds give as text.
+    pub fn reply_count(&self) -> u32 {
+        self.comment.trim().parse().unwrap_or_default()
+    }
+
     pub fn new(
         title: String,
         author: String,
@@ -30,17 +39,29 @@ impl Topic {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Reply {
+    /// Needed to thank the reply; 0 when the source did not say.
+    pub id: u64,
     pub author: String,
     pub time: String,
     pub content: String,
     pub number: String,
     pub reply_to: String,
     pub reply_count: i32,
+    pub thanks: u32,
+    /// Whether the logged-in member has thanked it.
+    pub thanked: bool,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl Reply {
+    /// The floor number, 0 if it could not be parsed.
+    pub fn floor(&self) -> u32 {
+        self.number.trim().parse().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TopicDetail {
     pub title: String,
     pub content: String,
@@ -49,4 +70,214 @@ pub struct TopicDetail {
     pub updated: String,
     pub link: String,
     pub replies: Vec<Reply>,
+    /// Number of reply pages; only the first one comes with the topic.
+    pub page_count: u32,
+    pub favorites: u32,
+    /// Whether the logged-in member has favorited or thanked the topic; the API
+    /// does not know, so these are only filled in from the HTML page.
+    pub favorited: bool,
+    pub thanked: bool,
+}
+
+/// The tabs of the V2EX home page, in the order the site shows them. The
+/// digit keys reach the first ten; R2 has a `tab11` command with no default key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tab {
+    Tech,
+    Creative,
+    Play,
+    Apple,
+    Jobs,
+    Deals,
+    City,
+    Qna,
+    Hot,
+    All,
+    R2,
+}
+
+impl Tab {
+    pub const TABS: [Tab; 11] = [
+        Tab::Tech,
+        Tab::Creative,
+        Tab::Play,
+        Tab::Apple,
+        Tab::Jobs,
+        Tab::Deals,
+        Tab::City,
+        Tab::Qna,
+        Tab::Hot,
+        Tab::All,
+        Tab::R2,
+    ];
+
+    /// The tab name used in V2EX urls, e.g. `/feed/tab/tech.xml`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Tab::Tech => "tech",
+            Tab::Creative => "creative",
+            Tab::Play => "play",
+            Tab::Apple => "apple",
+            Tab::Jobs => "jobs",
+            Tab::Deals => "deals",
+            Tab::City => "city",
+            Tab::Qna => "qna",
+            Tab::Hot => "hot",
+            Tab::All => "all",
+            Tab::R2 => "r2",
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Tab::Tech => msg().tab_tech,
+            Tab::Creative => msg().tab_creative,
+            Tab::Play => msg().tab_play,
+            Tab::Apple => "Apple",
+            Tab::Jobs => msg().tab_jobs,
+            Tab::Deals => msg().tab_deals,
+            Tab::City => msg().tab_city,
+            Tab::Qna => msg().tab_qna,
+            Tab::Hot => msg().tab_hot,
+            Tab::All => msg().tab_all,
+            Tab::R2 => "R2",
+        }
+    }
+
+    pub fn index(self) -> usize {
+        Tab::TABS
+            .iter()
+            .position(|tab| *tab == self)
+            .unwrap_or_default()
+    }
+}
+
+/// Where the home page topic table gets its topics from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Feed {
+    Tab(Tab),
+    Node { name: String, title: String },
+}
+
+impl Feed {
+    pub fn title(&self) -> &str {
+        match self {
+            Feed::Tab(tab) => tab.title(),
+            Feed::Node { title, .. } => title,
+        }
+    }
+
+    /// Whether V2EX has a paginated HTML listing behind the feed, so more
+    /// pages can be loaded.
+    pub fn has_listing(&self) -> bool {
+        matches!(self, Feed::Tab(Tab::All) | Feed::Node { .. })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    pub name: String,
+    pub title: String,
+    pub topics: u32,
+    pub header: String,
+}
+
+/// How V2EX should render the body of a new topic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Syntax {
+    #[default]
+    Default,
+    Markdown,
+}
+
+impl Syntax {
+    /// The value the `/write` form expects.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Syntax::Default => "default",
+            Syntax::Markdown => "markdown",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Syntax::Default => msg().syntax_default,
+            Syntax::Markdown => "Markdown",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Member {
+    pub id: u64,
+    pub username: String,
+    /// Join date, already formatted for display.
+    pub created: String,
+    pub tagline: String,
+    pub bio: String,
+    pub website: String,
+    pub github: String,
+    pub twitter: String,
+    pub location: String,
+}
+
+/// The outcome of claiming the daily login reward.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckIn {
+    /// False when today's reward had already been claimed.
+    pub claimed: bool,
+    /// Coins received, when the balance page says.
+    pub coins: Option<u32>,
+    /// Consecutive days with the reward claimed.
+    pub streak: u32,
+}
+
+/// A reply as listed on its author's profile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemberReply {
+    pub topic_title: String,
+    /// Topic url ending in `#replyN` when the profile says which floor it is.
+    pub topic_link: String,
+    pub content: String,
+    pub time: String,
+}
+
+/// Everything the member page shows about one member.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemberProfile {
+    pub member: Member,
+    pub topics: Vec<Topic>,
+    pub replies: Vec<MemberReply>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    Reply,
+    Mention,
+    Thank,
+    Favorite,
+    Other,
+}
+
+impl NotificationKind {
+    pub fn title(&self) -> &'static str {
+        match self {
+            NotificationKind::Reply => msg().kind_reply,
+            NotificationKind::Mention => msg().kind_mention,
+            NotificationKind::Thank => msg().kind_thank,
+            NotificationKind::Favorite => msg().kind_favorite,
+            NotificationKind::Other => msg().kind_other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub kind: NotificationKind,
+    pub member: String,
+    pub topic_title: String,
+    /// Topic url, usually ending in `#replyN` for the floor it refers to.
+    pub topic_link: String,
+    pub content: String,
+    pub time: String,
 }
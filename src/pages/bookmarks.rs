@@ -0,0 +1,413 @@
+use std::rc::Rc;
+
+use chrono::DateTime;
+
+use crate::{
+    action::Action,
+    i18n::msg,
+    keymap::{Command, Context, Keymap},
+    store::bookmarks::{Bookmark, BookmarkStore},
+    theme::Theme,
+    time::time_formatting::format_relative_time,
+    worker::Worker,
+};
+
+use super::{input::TextInput, widgets, Page, PageType};
+use crossterm::event::{Event, KeyCode};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Row, Table, TableState, Tabs},
+    Frame,
+};
+
+/// Topics saved for later, readable offline from the copy kept with each one.
+pub struct BookmarksPage {
+    store: BookmarkStore,
+    // `None` shows every bookmark, otherwise only those with the tag.
+    tag: Option<String>,
+    state: TableState,
+    // The tags of the selected bookmark while they are being edited.
+    tags_input: Option<TextInput>,
+    confirm_delete: bool,
+    // Snapshots on their way, for bookmarks being added or refreshed.
+    pending: usize,
+    notice: Option<String>,
+    tick: usize,
+    keymap: Rc<Keymap>,
+    worker: Worker,
+}
+
+impl BookmarksPage {
+    pub fn new(worker: Worker, keymap: Rc<Keymap>) -> Self {
+        BookmarksPage {
+            store: BookmarkStore::load(),
+            tag: None,
+            state: TableState::default().with_selected(0),
+            tags_input: None,
+            confirm_delete: false,
+            pending: 0,
+            notice: None,
+            tick: 0,
+            keymap,
+            worker,
+        }
+    }
+
+    /// Indices into the store of the bookmarks the tag filter lets through.
+    fn visible(&self) -> Vec<usize> {
+        self.store
+            .bookmarks()
+            .iter()
+            .enumerate()
+            .filter(|(_, bookmark)| {
+                self.tag
+                    .as_ref()
+                    .is_none_or(|tag| bookmark.tags.contains(tag))
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        self.state
+            .selected()
+            .and_then(|row| self.visible().get(row).copied())
+    }
+
+    fn selected(&self) -> Option<&Bookmark> {
+        self.selected_index()
+            .and_then(|index| self.store.bookmarks().get(index))
+    }
+
+    fn save(&mut self) -> Result<(), String> {
+        self.store
+            .save()
+            .map_err(|e| (msg().bookmark_save_failed)(&e.to_string()))
+            .inspect_err(|error| self.notice = Some(error.clone()))
+    }
+
+    /// Shows everything again once no bookmark has the filtered tag.
+    fn drop_stale_tag(&mut self) {
+        if let Some(tag) = &self.tag {
+            if !self.store.tags().contains(&tag.as_str()) {
+                self.tag = None;
+            }
+        }
+    }
+
+    /// Moves the tag filter by `step` through "all" followed by every tag.
+    fn cycle_tag(&mut self, step: isize) {
+        let mut filters: Vec<Option<String>> = vec![None];
+        filters.extend(
+            self.store
+                .tags()
+                .into_iter()
+                .map(|tag| Some(tag.to_string())),
+        );
+        let index = filters
+            .iter()
+            .position(|filter| *filter == self.tag)
+            .unwrap_or(0);
+        let next = (index as isize + step).rem_euclid(filters.len() as isize) as usize;
+        self.tag = filters.swap_remove(next);
+        self.state.select_first();
+    }
+}
+
+impl Page for BookmarksPage {
+    fn page_type(&self) -> PageType {
+        PageType::Bookmarks
+    }
+
+    fn init(&mut self) -> Option<Action> {
+        None
+    }
+
+    fn context(&self) -> Option<Context> {
+        Some(Context::Bookmarks)
+    }
+
+    fn captures_input(&self) -> bool {
+        self.tags_input.is_some() || self.confirm_delete
+    }
+
+    fn render(&mut self, frame: &mut Frame, theme: &Theme) {
+        let main_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Tags
+                Constraint::Min(0),    // Table
+                Constraint::Length(3), // Footer
+            ])
+            .margin(1)
+            .split(frame.area());
+
+        let mut filters = vec![msg().tab_all.to_string()];
+        filters.extend(self.store.tags().into_iter().map(|tag| format!("#{}", tag)));
+        let selected_filter = self.tag.as_ref().map_or(0, |tag| {
+            self.store
+                .tags()
+                .iter()
+                .position(|t| t == tag)
+                .map_or(0, |index| index + 1)
+        });
+        let tabs = Tabs::new(filters)
+            .select(selected_filter)
+            .style(theme.tab)
+            .highlight_style(theme.tab_selected);
+        frame.render_widget(tabs, main_layout[0]);
+
+        let header_cells = msg()
+            .bookmark_columns
+            .iter()
+            .map(|h| Span::styled(*h, theme.header));
+        let header = Row::new(header_cells).height(1).bottom_margin(1);
+        let visible = self.visible();
+        let rows: Vec<Row> = visible
+            .iter()
+            .map(|index| {
+                let bookmark = &self.store.bookmarks()[*index];
+                let added = DateTime::from_timestamp(bookmark.added, 0)
+                    .map(format_relative_time)
+                    .unwrap_or_default();
+                let tags: Vec<String> = bookmark
+                    .tags
+                    .iter()
+                    .map(|tag| format!("#{}", tag))
+                    .collect();
+                Row::new(vec![
+                    Span::styled(bookmark.snapshot.title.as_str(), theme.text),
+                    Span::styled(tags.join(" "), theme.accent),
+                    Span::styled(bookmark.snapshot.comment.as_str(), theme.count),
+                    Span::styled(added, theme.muted),
+                ])
+            })
+            .collect();
+        let status = if self.pending > 0 {
+            Span::styled(
+                format!(" {} {} ", widgets::spinner(self.tick), msg().saving),
+                theme.notice,
+            )
+        } else if let Some(notice) = &self.notice {
+            Span::styled(format!(" {} ", notice), theme.notice)
+        } else {
+            "".into()
+        };
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Percentage(60),
+                Constraint::Percentage(15),
+                Constraint::Percentage(10),
+                Constraint::Percentage(15),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::default()
+                .title(format!(
+                    " {} ",
+                    (msg().bookmarks_title)(visible.len(), self.store.bookmarks().len())
+                ))
+                .title_alignment(Alignment::Center)
+                .title_bottom(Line::from(status))
+                .borders(Borders::ALL)
+                .style(theme.border),
+        )
+        .column_spacing(1)
+        .row_highlight_style(theme.selection);
+        frame.render_stateful_widget(table, main_layout[1], &mut self.state);
+
+        let footer_text = if self.tags_input.is_some() {
+            msg().tags_hint.to_string()
+        } else {
+            self.keymap.hints(
+                Context::Bookmarks,
+                &[
+                    (msg().hint.quit, &[Command::Quit]),
+                    (msg().hint.help, &[Command::Help]),
+                    (msg().hint.back, &[Command::Back]),
+                    (msg().hint.scroll, &[Command::Up, Command::Down]),
+                    (
+                        msg().hint.filter_tags,
+                        &[Command::PreviousTab, Command::NextTab],
+                    ),
+                    (msg().hint.read_offline, &[Command::Open]),
+                    (msg().hint.refresh_snapshot, &[Command::Reload]),
+                    (msg().hint.edit_tags, &[Command::EditTags]),
+                    (msg().hint.delete, &[Command::Delete]),
+                    (msg().hint.open_browser, &[Command::OpenBrowser]),
+                ],
+            )
+        };
+        let footer = Paragraph::new(Line::from(Span::styled(footer_text, theme.footer)))
+            .alignment(Alignment::Left)
+            .block(Block::default().borders(Borders::ALL).style(theme.border));
+        frame.render_widget(footer, main_layout[2]);
+
+        if let Some(input) = &self.tags_input {
+            let area = widgets::centered(frame.area(), 60, 3);
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} ", msg().tags_title))
+                .style(theme.notice);
+            frame.render_widget(Clear, area);
+            input.render(frame, area, block, true);
+        }
+        if self.confirm_delete {
+            if let Some(bookmark) = self.selected() {
+                let prompt = (msg().delete_bookmark_prompt)(&bookmark.snapshot.title);
+                widgets::confirm_popup(frame, &prompt, theme);
+            }
+        }
+    }
+
+    fn handle_event(&mut self, event: Event) -> Option<Action> {
+        let Event::Key(key) = event else {
+            return None;
+        };
+        if self.tags_input.is_some() {
+            return match key.code {
+                KeyCode::Enter => Some(Action::SaveTags),
+                KeyCode::Esc => Some(Action::CloseComposer),
+                _ => Some(Action::ComposerInput(key)),
+            };
+        }
+        if !self.confirm_delete {
+            return None;
+        }
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => Some(Action::Confirm),
+            KeyCode::Char('n') | KeyCode::Esc => Some(Action::CancelConfirm),
+            _ => None,
+        }
+    }
+
+    fn handle_command(&mut self, command: Command) -> Option<Action> {
+        match command {
+            Command::Back => Some(Action::GoBack),
+            Command::Top => Some(Action::Top),
+            Command::Bottom => Some(Action::Bottom),
+            Command::Up => Some(Action::PreviousRow),
+            Command::Down => Some(Action::NextRow),
+            Command::PreviousTab => Some(Action::PreviousTab),
+            Command::NextTab => Some(Action::NextTab),
+            Command::Reload => self.selected().map(|_| Action::RefreshBookmark),
+            Command::EditTags => self.selected().map(|_| Action::EditTags),
+            Command::Delete => self.selected().map(|_| Action::DeleteBookmark),
+            Command::OpenBrowser => self
+                .selected()
+                .map(|bookmark| Action::OpenBrowser(bookmark.url.clone())),
+            Command::Open => self.selected().map(|bookmark| {
+                Action::OpenSnapshot(bookmark.url.clone(), bookmark.snapshot.clone())
+            }),
+            _ => None,
+        }
+    }
+
+    fn update(&mut self, action: Action) -> Option<Action> {
+        match action {
+            Action::AddBookmark(url) => {
+                self.pending += 1;
+                self.worker.fetch_topic_snapshot(url);
+                None
+            }
+            Action::SnapshotLoaded(url, result) => {
+                self.pending = self.pending.saturating_sub(1);
+                match result {
+                    Ok(snapshot) => Some(Action::SaveBookmark(url, snapshot)),
+                    Err(error) => {
+                        let error = (msg().bookmark_save_failed)(&error.message);
+                        self.notice = Some(error.clone());
+                        Some(Action::BookmarkSaved(Err(error)))
+                    }
+                }
+            }
+            Action::SaveBookmark(url, snapshot) => {
+                let title = snapshot.title.clone();
+                self.store.put(&url, snapshot);
+                let result = self.save().map(|()| title);
+                if let Ok(title) = &result {
+                    self.notice = Some((msg().bookmark_saved)(title));
+                }
+                Some(Action::BookmarkSaved(result))
+            }
+            Action::RefreshBookmark => {
+                let url = self.selected()?.url.clone();
+                self.pending += 1;
+                self.worker.fetch_topic_snapshot(url);
+                None
+            }
+            Action::EditTags => {
+                let tags = self.selected()?.tags.join(" ");
+                self.tags_input = Some(TextInput::new(&tags));
+                None
+            }
+            Action::ComposerInput(key) => {
+                self.tags_input.as_mut()?.handle_key(key);
+                None
+            }
+            Action::CloseComposer => {
+                self.tags_input = None;
+                None
+            }
+            Action::SaveTags => {
+                let tags = self.tags_input.take()?.text();
+                let index = self.selected_index()?;
+                self.store.set_tags(index, &tags);
+                self.drop_stale_tag();
+                let _ = self.save();
+                None
+            }
+            Action::DeleteBookmark => {
+                self.confirm_delete = self.selected().is_some();
+                None
+            }
+            Action::CancelConfirm => {
+                self.confirm_delete = false;
+                None
+            }
+            Action::Confirm => {
+                self.confirm_delete = false;
+                let index = self.selected_index()?;
+                if let Some(bookmark) = self.store.remove(index) {
+                    if self.save().is_ok() {
+                        self.notice = Some((msg().bookmark_deleted)(&bookmark.snapshot.title));
+                    }
+                }
+                self.drop_stale_tag();
+                None
+            }
+            Action::PreviousTab => {
+                self.cycle_tag(-1);
+                None
+            }
+            Action::NextTab => {
+                self.cycle_tag(1);
+                None
+            }
+            Action::Top => {
+                self.state.select_first();
+                None
+            }
+            Action::Bottom => {
+                self.state.select_last();
+                None
+            }
+            Action::PreviousRow => {
+                self.state.select_previous();
+                None
+            }
+            Action::NextRow => {
+                self.state.select_next();
+                None
+            }
+            Action::Tick => {
+                self.tick = self.tick.wrapping_add(1);
+                None
+            }
+            _ => None,
+        }
+    }
+}
@@ -1,33 +1,227 @@
-use crate::{action::Action, api::Crawler, model::TopicDetail};
+use std::{collections::BTreeSet, rc::Rc};
 
-use super::{Page, PageType};
-use crossterm::event::{Event, KeyCode, MouseEventKind};
+use crate::{
+    action::Action,
+    api::{merge_replies, reply_floor, FetchError, REPLIES_PER_PAGE},
+    export,
+    i18n::msg,
+    keymap::{Command, Context, Keymap},
+    model::{Reply, TopicDetail},
+    theme::Theme,
+    worker::Worker,
+};
+
+use super::{input::TextInput, widgets, Page, PageType};
+use crossterm::event::{Event, KeyCode, KeyModifiers, MouseEventKind};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
-    style::{Color, Modifier, Style, Stylize},
+    style::Modifier,
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
 };
 
+/// A reply being written. It outlives a failed submit and being folded away,
+/// and is only dropped once sent or when replying to another topic.
+struct Composer {
+    url: String,
+    input: TextInput,
+    open: bool,
+    sending: bool,
+    error: Option<String>,
+}
+
+/// An action that costs coins or hides the topic, waiting for confirmation.
+#[derive(Clone, Copy)]
+enum Pending {
+    ThankReply(u64),
+    ThankTopic,
+    Favorite(bool),
+    Ignore,
+}
+
 pub struct DetailPage {
     loading: bool,
+    tick: usize,
+    // The topic being shown or fetched; results for any other url are stale.
+    url: Option<String>,
     topic_detail: Option<TopicDetail>,
-    crawler: Crawler,
+    error: Option<FetchError>,
+    // Reply pages merged into `topic_detail` and those still on their way.
+    loaded_pages: BTreeSet<u32>,
+    pending_pages: BTreeSet<u32>,
+    page_error: Option<(u32, FetchError)>,
+    composer: Option<Composer>,
+    // Set after replying, so the view follows the new floor to the end.
+    jump_to_end: bool,
+    // The reply picked with n/p, by floor; thanks go to it rather than the topic.
+    selected: Option<u32>,
+    follow_selection: bool,
+    // The floor a `#replyN` url asked for, until its page has arrived.
+    pending_floor: Option<u32>,
+    confirm: Option<(Pending, String)>,
+    // Outcome of the last thank/favorite/ignore, shown under the footer.
+    notice: Option<String>,
+    // Showing a bookmark's saved copy rather than the live topic.
+    offline: bool,
+    // Where a resumed topic was left, applied once it has been laid out.
+    pending_scroll: Option<u16>,
+    keymap: Rc<Keymap>,
+    worker: Worker,
     max_scroll: u16,
     scroll: u16,
+    scroll_step: u16,
+    view_height: u16,
 }
 
 impl DetailPage {
-    pub fn new() -> Self {
+    pub fn new(worker: Worker, keymap: Rc<Keymap>, scroll_step: u16) -> Self {
         DetailPage {
             loading: true,
+            tick: 0,
+            url: None,
             topic_detail: None,
-            crawler: Crawler::new(),
+            error: None,
+            loaded_pages: BTreeSet::new(),
+            pending_pages: BTreeSet::new(),
+            page_error: None,
+            composer: None,
+            jump_to_end: false,
+            selected: None,
+            follow_selection: false,
+            pending_floor: None,
+            confirm: None,
+            notice: None,
+            offline: false,
+            pending_scroll: None,
+            keymap,
+            worker,
             max_scroll: 0,
             scroll: 0,
+            scroll_step,
+            view_height: 0,
+        }
+    }
+
+    fn page_count(&self) -> u32 {
+        self.topic_detail
+            .as_ref()
+            .map_or(1, |detail| detail.page_count)
+    }
+
+    fn next_page(&self) -> Option<u32> {
+        (2..=self.page_count())
+            .find(|page| !self.loaded_pages.contains(page) && !self.pending_pages.contains(page))
+    }
+
+    /// Asks for the next reply page once the reader gets within a screen of the end.
+    fn load_more_if_near_end(&self) -> Option<Action> {
+        let near_end = self.scroll.saturating_add(self.view_height) >= self.max_scroll;
+        if near_end && self.pending_pages.is_empty() && self.page_error.is_none() {
+            self.next_page().map(Action::FetchReplyPage)
+        } else {
+            None
+        }
+    }
+
+    fn fetch_all_pages(&mut self) {
+        let Some(url) = self.url.clone() else {
+            return;
+        };
+        self.page_error = None;
+        while let Some(page) = self.next_page() {
+            self.pending_pages.insert(page);
+            self.worker.fetch_topic_replies(url.clone(), page);
+        }
+    }
+
+    fn open_composer(&self) -> Option<&Composer> {
+        self.composer.as_ref().filter(|composer| composer.open)
+    }
+
+    /// The topic being left and where, for the history.
+    fn visit(&self) -> Option<Action> {
+        Some(Action::TopicVisited {
+            url: self.url.clone()?,
+            title: self.topic_detail.as_ref()?.title.clone(),
+            scroll: self.scroll,
+        })
+    }
+
+    /// Tells the read store how far the loaded replies go.
+    fn topic_read(&self) -> Option<Action> {
+        let detail = self.topic_detail.as_ref()?;
+        Some(Action::TopicRead {
+            url: self.url.clone()?,
+            replies: detail.comment.trim().parse().unwrap_or_default(),
+            floor: detail
+                .replies
+                .iter()
+                .map(Reply::floor)
+                .max()
+                .unwrap_or_default(),
+        })
+    }
+
+    fn selected_reply(&self) -> Option<&Reply> {
+        let floor = self.selected?;
+        self.topic_detail
+            .as_ref()?
+            .replies
+            .iter()
+            .find(|reply| reply.floor() == floor)
+    }
+
+    /// Moves the reply cursor to the next or previous loaded reply, asking for
+    /// the next page once it reaches the last one.
+    fn select_reply(&mut self, forward: bool) -> Option<Action> {
+        let replies = &self.topic_detail.as_ref()?.replies;
+        let index = self
+            .selected
+            .and_then(|floor| replies.iter().position(|reply| reply.floor() == floor));
+        let index = match (index, forward) {
+            (Some(index), true) => (index + 1).min(replies.len().checked_sub(1)?),
+            (Some(index), false) => index.checked_sub(1)?,
+            (None, true) => 0,
+            (None, false) => return None,
+        };
+        self.selected = Some(replies.get(index)?.floor());
+        self.follow_selection = true;
+        if index + 1 == replies.len() && self.pending_pages.is_empty() {
+            self.next_page().map(Action::FetchReplyPage)
+        } else {
+            None
+        }
+    }
+
+    /// Selects the floor the url pointed at once it is loaded, fetching its page if needed.
+    fn seek_floor(&mut self) {
+        let (Some(floor), Some(url)) = (self.pending_floor, self.url.clone()) else {
+            return;
+        };
+        let Some(detail) = &self.topic_detail else {
+            return;
+        };
+        if detail.replies.iter().any(|reply| reply.floor() == floor) {
+            self.selected = Some(floor);
+            self.follow_selection = true;
+            self.pending_floor = None;
+            return;
+        }
+        let page = (floor.max(1) - 1) / REPLIES_PER_PAGE + 1;
+        if page > self.page_count() || self.loaded_pages.contains(&page) {
+            // The floor is gone, e.g. a deleted reply.
+            self.pending_floor = None;
+        } else if self.pending_pages.insert(page) {
+            self.worker.fetch_topic_replies(url, page);
         }
     }
+
+    fn reset_pages(&mut self) {
+        self.loaded_pages.clear();
+        self.pending_pages.clear();
+        self.page_error = None;
+    }
 }
 
 impl Page for DetailPage {
@@ -35,139 +229,621 @@ impl Page for DetailPage {
         PageType::Detail
     }
 
+    fn context(&self) -> Option<Context> {
+        Some(Context::Detail)
+    }
+
     fn init(&mut self) -> Option<Action> {
-        self.loading = true;
-        self.topic_detail = None;
-        self.scroll = 0;
         None
     }
 
-    fn render(&mut self, frame: &mut Frame) {
+    /// Hands over where the topic was left, for the history.
+    fn leave(&mut self) -> Option<Action> {
+        self.visit()
+    }
+
+    fn captures_input(&self) -> bool {
+        self.open_composer().is_some() || self.confirm.is_some()
+    }
+
+    fn render(&mut self, frame: &mut Frame, theme: &Theme) {
+        let composer_height = if self.open_composer().is_some() {
+            10
+        } else {
+            0
+        };
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Min(0), Constraint::Length(3)])
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(composer_height),
+                Constraint::Length(3),
+            ])
             .split(frame.area());
 
         if self.loading {
-            let loading_text = "Loading...";
-            let paragraph = Paragraph::new(loading_text);
+            let paragraph = Paragraph::new(widgets::loading_line(self.tick, theme));
             frame.render_widget(paragraph, chunks[0]);
             return;
         }
 
+        if let Some(error) = &self.error {
+            let hint = self.keymap.hints(
+                Context::Detail,
+                &[
+                    (msg().hint.retry, &[Command::Reload]),
+                    (msg().hint.back, &[Command::Back]),
+                    (msg().hint.open_browser, &[Command::OpenBrowser]),
+                ],
+            );
+            frame.render_widget(widgets::error_paragraph(error, &hint, theme), frame.area());
+            return;
+        }
+
         let detail = self.topic_detail.as_ref().unwrap();
-        let bold_cyan = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
-        let gray = Style::default().fg(Color::Gray);
-        
         let mut lines = vec![
             Line::from(vec![
-                Span::styled("话题：", gray),
-                Span::styled(&detail.title, bold_cyan),
+                Span::styled(msg().topic_label, theme.secondary),
+                Span::styled(&detail.title, theme.title),
             ]),
             Line::from(vec![
-                Span::styled("楼主：", gray),
-                Span::styled(&detail.author, bold_cyan),
+                Span::styled(msg().author_label, theme.secondary),
+                Span::styled(&detail.author, theme.op),
             ]),
             Line::from(vec![
-                Span::styled("活跃时间：", gray),
-                Span::styled(&detail.updated, bold_cyan),
+                Span::styled(msg().active_label, theme.secondary),
+                Span::styled(&detail.updated, theme.title),
             ]),
-            Line::from(vec![Span::styled("内容：", gray)]),
-            Line::from(vec![Span::styled(&detail.content, bold_cyan)]),
-            Line::from(vec![Span::styled("评论：", gray)]),
         ];
+        let mut marks = vec![];
+        if detail.favorites > 0 {
+            marks.push((msg().favorites)(detail.favorites));
+        }
+        if detail.favorited {
+            marks.push(msg().favorited.to_string());
+        }
+        if detail.thanked {
+            marks.push(msg().thanked.to_string());
+        }
+        if self.offline {
+            let hint = self
+                .keymap
+                .hints(Context::Detail, &[(msg().hint.reload, &[Command::Reload])]);
+            marks.push((msg().offline_copy)(&hint));
+        }
+        if !marks.is_empty() {
+            lines.push(Line::from(vec![
+                Span::styled(msg().marks_label, theme.secondary),
+                Span::styled(marks.join(" · "), theme.notice),
+            ]));
+        }
+        lines.extend([
+            Line::from(vec![Span::styled(msg().content_label, theme.secondary)]),
+            Line::from(vec![Span::styled(&detail.content, theme.reply)]),
+            Line::from(vec![Span::styled(
+                (msg().replies_heading)(self.loaded_pages.len().max(1) as u32, detail.page_count),
+                theme.secondary,
+            )]),
+        ]);
 
+        // Where the selected reply starts, so the view can follow the cursor.
+        let mut selected_line = None;
         self.topic_detail
             .as_ref()
             .unwrap()
             .replies
             .iter()
             .for_each(|reply| {
-                lines.push(Line::from(vec![Span::styled(
-                    "",
-                    Style::default()
-                        .fg(Color::Cyan)
-                        .add_modifier(Modifier::BOLD),
-                )]));
-                lines.push(Line::from(vec![Span::styled(
-                    format!(">>> {} 回复于 {}:", reply.author, reply.time),
-                    Style::default()
-                        .fg(Color::Cyan)
-                        .add_modifier(Modifier::BOLD),
-                )]));
-                lines.push(Line::from(vec![Span::styled(
-                    &reply.content,
-                    Style::default()
-                        .fg(Color::Cyan)
-                        .add_modifier(Modifier::BOLD),
-                )]));
+                lines.push(Line::from(""));
+                let mut header = (msg().reply_header)(&reply.author, &reply.time);
+                if reply.thanks > 0 {
+                    header.push_str(&format!(" ♥ {}", reply.thanks));
+                }
+                if reply.thanked {
+                    header.push_str(msg().reply_thanked);
+                }
+                // Replies by the topic's author stand out, as on the site.
+                let mut style = if reply.author == detail.author {
+                    theme.op
+                } else {
+                    theme.reply
+                };
+                if self.selected == Some(reply.floor()) {
+                    selected_line = Some(lines.len());
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                lines.push(Line::from(vec![Span::styled(header, style)]));
+                lines.push(Line::from(vec![Span::styled(&reply.content, theme.reply)]));
             });
 
+        lines.push(Line::from(""));
+        if let Some(page) = self.pending_pages.first() {
+            lines.push(Line::from(vec![Span::styled(
+                format!(
+                    "{} {}",
+                    widgets::spinner(self.tick),
+                    (msg().loading_page)(*page)
+                ),
+                theme.notice,
+            )]));
+        } else if let Some((page, error)) = &self.page_error {
+            let hint = self
+                .keymap
+                .hints(Context::Detail, &[(msg().hint.retry, &[Command::Reload])]);
+            lines.push(Line::from(vec![Span::styled(
+                (msg().page_failed)(*page, &error.message, &hint),
+                theme.error,
+            )]));
+        } else if self.next_page().is_some() {
+            let remaining = detail.page_count - self.loaded_pages.len().max(1) as u32;
+            let hint = self.keymap.hints(
+                Context::Detail,
+                &[(msg().hint.load_all, &[Command::LoadAll])],
+            );
+            lines.push(Line::from(vec![Span::styled(
+                (msg().more_pages)(remaining, &hint),
+                theme.muted,
+            )]));
+        }
+
         let area = chunks[0];
+        let selected_offset = selected_line.filter(|_| self.follow_selection).map(|end| {
+            lines[..end]
+                .iter()
+                .map(|line| {
+                    Paragraph::new(line.clone())
+                        .wrap(Wrap { trim: true })
+                        .line_count(area.width)
+                })
+                .sum::<usize>() as u16
+        });
         let text = ratatui::text::Text::from(lines);
 
-        let paragraph = Paragraph::new(text)
-            .wrap(Wrap { trim: true })
-            .scroll((self.scroll, 0));
+        let paragraph = Paragraph::new(text).wrap(Wrap { trim: true });
         let line_count = paragraph.line_count(area.width) as u16;
         self.max_scroll = line_count.saturating_sub(area.height);
-        frame.render_widget(paragraph, area);
+        self.view_height = area.height;
+        if let Some(scroll) = self.pending_scroll.take() {
+            self.scroll = scroll.min(self.max_scroll);
+        }
+        if self.jump_to_end {
+            self.scroll = self.max_scroll;
+            self.jump_to_end = !self.pending_pages.is_empty();
+        }
+        if let Some(offset) = selected_offset {
+            self.scroll = offset.min(self.max_scroll);
+            self.follow_selection = false;
+        }
+        frame.render_widget(paragraph.scroll((self.scroll, 0)), area);
+
+        if let Some(composer) = self.open_composer() {
+            let status = if composer.sending {
+                Span::styled(
+                    format!(" {} {} ", widgets::spinner(self.tick), msg().sending),
+                    theme.notice,
+                )
+            } else if let Some(error) = &composer.error {
+                Span::styled(format!(" {} ", (msg().send_failed)(error)), theme.error)
+            } else {
+                Span::styled(format!(" {} ", msg().multiline), theme.muted)
+            };
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} ", (msg().reply_title)(&detail.title)))
+                .title_bottom(Line::from(status))
+                .style(theme.notice);
+            composer.input.render(frame, chunks[1], block, true);
+        }
 
         // Render footer with help text
-        let footer_text = Line::from(vec![
-            "退出：q｜返回：Esc/Backspace｜滚动：↑↓jk｜移到顶部：t｜移到底部：b｜浏览器打开：o"
-                .cyan()
-                .bold(),
-        ]);
+        let footer_text = if self.open_composer().is_some() {
+            msg().composer_hint.to_string()
+        } else {
+            self.keymap.hints(
+                Context::Detail,
+                &[
+                    (msg().hint.quit, &[Command::Quit]),
+                    (msg().hint.help, &[Command::Help]),
+                    (msg().hint.back, &[Command::Back]),
+                    (msg().hint.scroll, &[Command::Up, Command::Down]),
+                    (msg().hint.reply, &[Command::Reply]),
+                ],
+            )
+        };
+        let footer_text = Line::from(vec![Span::styled(footer_text, theme.footer)]);
+        let notice = self.notice.as_deref().map_or(Line::default(), |notice| {
+            Line::from(Span::styled(format!(" {} ", notice), theme.notice))
+        });
         let footer = Paragraph::new(footer_text)
             .alignment(Alignment::Left)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .style(Style::default().fg(Color::Cyan)),
+                    .title_bottom(notice)
+                    .style(theme.border),
             );
-        frame.render_widget(footer, chunks[1]);
+        frame.render_widget(footer, chunks[2]);
+
+        if let Some((_, prompt)) = &self.confirm {
+            widgets::confirm_popup(frame, prompt, theme);
+        }
     }
 
     fn handle_event(&mut self, event: Event) -> Option<Action> {
-        match event {
-            Event::Key(key) => match key.code {
-                KeyCode::Esc | KeyCode::Backspace => Some(Action::GoHome),
-                KeyCode::Char('o') => self
-                    .topic_detail
-                    .as_ref()
-                    .map(|detail| Action::OpenBrowser(detail.link.clone())),
-                KeyCode::Char('t') => Some(Action::Top),
-                KeyCode::Char('b') => Some(Action::Bottom),
-                KeyCode::Up | KeyCode::Char('k') => Some(Action::LineUp(3)),
-                KeyCode::Down | KeyCode::Char('j') => Some(Action::LineDown(3)),
-
+        if self.confirm.is_some() {
+            let Event::Key(key) = event else {
+                return None;
+            };
+            return match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => Some(Action::Confirm),
+                KeyCode::Char('n') | KeyCode::Esc => Some(Action::CancelConfirm),
                 _ => None,
-            },
+            };
+        }
+        if let Some(composer) = self.open_composer() {
+            let Event::Key(key) = event else {
+                return None;
+            };
+            let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+            return match key.code {
+                KeyCode::Esc => Some(Action::CloseComposer),
+                _ if composer.sending => None,
+                KeyCode::Char('s') if ctrl => Some(Action::SubmitReply),
+                KeyCode::Char('e') if ctrl => Some(Action::OpenEditor(composer.input.text())),
+                _ => Some(Action::ComposerInput(key)),
+            };
+        }
+        match event {
             Event::Mouse(mouse_event) => match mouse_event.kind {
-                MouseEventKind::ScrollUp => Some(Action::LineUp(3)),
-                MouseEventKind::ScrollDown => Some(Action::LineDown(3)),
+                MouseEventKind::ScrollUp => Some(Action::LineUp(self.scroll_step)),
+                MouseEventKind::ScrollDown => Some(Action::LineDown(self.scroll_step)),
                 _ => None,
             },
             _ => None,
         }
     }
 
+    fn handle_command(&mut self, command: Command) -> Option<Action> {
+        match command {
+            Command::Back => Some(Action::GoBack),
+            Command::OpenBrowser => self.url.clone().map(Action::OpenBrowser),
+            Command::Reply => self.topic_detail.is_some().then_some(Action::Compose),
+            Command::LoadAll => Some(Action::FetchAllReplyPages),
+            Command::NextReply => Some(Action::NextRow),
+            Command::PreviousReply => Some(Action::PreviousRow),
+            Command::Thank => Some(Action::Thank),
+            Command::Favorite => Some(Action::ToggleFavorite),
+            Command::Ignore => Some(Action::IgnoreTopic),
+            // The bookmarks page fetches every reply page, not just the loaded ones.
+            Command::Bookmark => self
+                .topic_detail
+                .as_ref()
+                .and(self.url.clone())
+                .map(Action::AddBookmark),
+            // A reply page that failed to load is retried on its own.
+            Command::Reload => match &self.page_error {
+                Some((page, _)) if self.error.is_none() => Some(Action::FetchReplyPage(*page)),
+                _ => Some(Action::Reload),
+            },
+            // The selected reply's author, or the topic's when none is selected.
+            Command::Author => self
+                .selected_reply()
+                .map(|reply| reply.author.clone())
+                .or_else(|| {
+                    self.topic_detail
+                        .as_ref()
+                        .map(|detail| detail.author.clone())
+                })
+                .map(Action::OpenMember),
+            Command::Top => Some(Action::Top),
+            Command::Bottom => Some(Action::Bottom),
+            Command::Up => Some(Action::LineUp(self.scroll_step)),
+            Command::Down => Some(Action::LineDown(self.scroll_step)),
+            _ => None,
+        }
+    }
+
     fn update(&mut self, action: Action) -> Option<Action> {
         match action {
-            Action::GoHome => self.init(),
+            Action::ResumeTopic { url, scroll } => {
+                let visit = self.update(Action::FetchTopicDetail(url));
+                self.pending_scroll = Some(scroll);
+                visit
+            }
             Action::FetchTopicDetail(url) => {
+                let visit = self.visit();
+                self.loading = true;
+                self.topic_detail = None;
+                self.error = None;
+                self.reset_pages();
+                self.scroll = 0;
+                self.selected = None;
+                self.notice = None;
+                self.offline = false;
+                self.pending_scroll = None;
+                self.pending_floor = reply_floor(&url);
+                self.url = Some(url.clone());
+                self.worker.fetch_topic_detail(url);
+                visit
+            }
+            Action::OpenSnapshot(url, detail) => {
+                let visit = self.visit();
                 self.loading = false;
-                self.topic_detail = Some(self.crawler.fetch_topic_detail(&url).unwrap());
+                self.error = None;
+                self.reset_pages();
+                // Only the pages the copy has replies from count as loaded.
+                self.loaded_pages.insert(1);
+                self.loaded_pages.extend(
+                    detail
+                        .replies
+                        .iter()
+                        .map(|reply| reply.floor().saturating_sub(1) / REPLIES_PER_PAGE + 1),
+                );
+                self.topic_detail = Some(detail);
+                self.scroll = 0;
+                self.selected = None;
+                self.notice = None;
+                self.offline = true;
+                self.pending_scroll = None;
+                self.pending_floor = None;
+                self.url = Some(url);
+                visit
+            }
+            Action::ExportTopic { format, path } => {
+                self.notice = Some(match &self.topic_detail {
+                    Some(detail) => match export::write(detail, format, path.as_deref()) {
+                        Ok(path) => (msg().exported)(&path.display().to_string()),
+                        Err(e) => (msg().export_failed)(&e.to_string()),
+                    },
+                    None => msg().export_no_topic.to_string(),
+                });
+                None
+            }
+            Action::BookmarkSaved(result) => {
+                self.notice = Some(match result {
+                    Ok(title) => (msg().bookmark_added)(&title),
+                    Err(error) => error,
+                });
+                None
+            }
+            Action::TopicDetailLoaded(url, result) => {
+                if self.url.as_ref() == Some(&url) {
+                    self.loading = false;
+                    match result {
+                        Ok(detail) => {
+                            self.loaded_pages.insert(1);
+                            self.topic_detail = Some(detail);
+                            if self.jump_to_end {
+                                self.fetch_all_pages();
+                            }
+                            self.seek_floor();
+                            return self.topic_read();
+                        }
+                        Err(error) => self.error = Some(error),
+                    }
+                }
+                None
+            }
+            Action::FetchReplyPage(page) => {
+                let url = self.url.clone()?;
+                if self.loaded_pages.contains(&page) || !self.pending_pages.insert(page) {
+                    return None;
+                }
+                self.page_error = None;
+                self.worker.fetch_topic_replies(url, page);
+                None
+            }
+            Action::FetchAllReplyPages => {
+                self.fetch_all_pages();
+                None
+            }
+            Action::RepliesLoaded(url, page, result) => {
+                if self.url.as_ref() != Some(&url) || !self.pending_pages.remove(&page) {
+                    return None;
+                }
+                let detail = self.topic_detail.as_mut()?;
+                match result {
+                    Ok(replies) => {
+                        detail.replies.extend(replies);
+                        merge_replies(&mut detail.replies);
+                        self.loaded_pages.insert(page);
+                        self.seek_floor();
+                        return self.topic_read();
+                    }
+                    Err(error) => self.page_error = Some((page, error)),
+                }
+                None
+            }
+            Action::Reload => self.url.clone().map(Action::FetchTopicDetail),
+            Action::Compose => {
+                let url = self.url.clone()?;
+                match &mut self.composer {
+                    Some(composer) if composer.url == url => composer.open = true,
+                    _ => {
+                        self.composer = Some(Composer {
+                            url,
+                            input: TextInput::default(),
+                            open: true,
+                            sending: false,
+                            error: None,
+                        })
+                    }
+                }
+                None
+            }
+            Action::ComposerInput(key) => {
+                self.composer.as_mut()?.input.handle_key(key);
+                None
+            }
+            Action::CloseComposer => {
+                self.composer.as_mut()?.open = false;
+                None
+            }
+            Action::EditorClosed(result) => {
+                let composer = self.composer.as_mut().filter(|c| c.open)?;
+                match result {
+                    Ok(text) => composer.input = TextInput::new(&text),
+                    Err(error) => composer.error = Some(error),
+                }
+                None
+            }
+            Action::SubmitReply => {
+                let composer = self.composer.as_mut()?;
+                if composer.input.is_blank() {
+                    composer.error = Some(msg().empty_reply.to_string());
+                    return None;
+                }
+                composer.sending = true;
+                composer.error = None;
+                self.worker
+                    .post_reply(composer.url.clone(), composer.input.text());
+                None
+            }
+            Action::ReplyPosted(url, result) => {
+                let composer = self.composer.as_mut().filter(|c| c.url == url)?;
+                composer.sending = false;
+                if let Err(error) = result {
+                    composer.error = Some(error.message);
+                    return None;
+                }
+                self.composer = None;
+                if self.url.as_ref() != Some(&url) {
+                    return None;
+                }
+                // Reloaded here, since the reader may have moved to another page.
+                self.jump_to_end = true;
+                self.update(Action::FetchTopicDetail(url))
+            }
+            Action::Tick => {
+                self.tick = self.tick.wrapping_add(1);
                 None
             }
             Action::Top => {
                 self.scroll = 0;
+                self.selected = None;
+                None
+            }
+            Action::NextRow => self.select_reply(true),
+            Action::PreviousRow => self.select_reply(false),
+            Action::Thank => {
+                let detail = self.topic_detail.as_ref()?;
+                let pending = match self.selected_reply() {
+                    Some(reply) if reply.thanked => Err(msg().reply_already_thanked),
+                    Some(reply) if reply.id == 0 => Err(msg().reply_without_id),
+                    Some(reply) => Ok((
+                        Pending::ThankReply(reply.id),
+                        (msg().thank_reply_prompt)(&reply.author, reply.floor()),
+                    )),
+                    None if detail.thanked => Err(msg().topic_already_thanked),
+                    None => Ok((
+                        Pending::ThankTopic,
+                        (msg().thank_topic_prompt)(&detail.author),
+                    )),
+                };
+                match pending {
+                    Ok(pending) => self.confirm = Some(pending),
+                    Err(notice) => self.notice = Some(notice.to_string()),
+                }
+                None
+            }
+            Action::ToggleFavorite => {
+                let favorite = !self.topic_detail.as_ref()?.favorited;
+                let prompt = if favorite {
+                    msg().favorite_prompt
+                } else {
+                    msg().unfavorite_prompt
+                };
+                self.confirm = Some((Pending::Favorite(favorite), prompt.to_string()));
                 None
             }
+            Action::IgnoreTopic => {
+                self.topic_detail.as_ref()?;
+                let prompt = msg().ignore_prompt;
+                self.confirm = Some((Pending::Ignore, prompt.to_string()));
+                None
+            }
+            Action::CancelConfirm => {
+                self.confirm = None;
+                None
+            }
+            // Counts change right away and are put back if the request fails.
+            Action::Confirm => {
+                let (pending, _) = self.confirm.take()?;
+                let url = self.url.clone()?;
+                let detail = self.topic_detail.as_mut()?;
+                self.notice = None;
+                match pending {
+                    Pending::ThankReply(id) => {
+                        let reply = detail.replies.iter_mut().find(|reply| reply.id == id)?;
+                        reply.thanks += 1;
+                        reply.thanked = true;
+                        self.worker.thank(url, Some(id));
+                    }
+                    Pending::ThankTopic => {
+                        detail.thanked = true;
+                        self.worker.thank(url, None);
+                    }
+                    Pending::Favorite(favorite) => {
+                        set_favorite(detail, favorite);
+                        self.worker.favorite_topic(url, favorite);
+                    }
+                    Pending::Ignore => {
+                        self.notice = Some(msg().ignoring.to_string());
+                        self.worker.ignore_topic(url);
+                    }
+                }
+                None
+            }
+            Action::ThankSent(url, reply_id, result) => {
+                if self.url.as_ref() != Some(&url) {
+                    return None;
+                }
+                let Err(error) = result else {
+                    self.notice = Some(msg().thanks_sent.to_string());
+                    return None;
+                };
+                let detail = self.topic_detail.as_mut()?;
+                match reply_id {
+                    Some(id) => {
+                        if let Some(reply) = detail.replies.iter_mut().find(|r| r.id == id) {
+                            reply.thanks = reply.thanks.saturating_sub(1);
+                            reply.thanked = false;
+                        }
+                    }
+                    None => detail.thanked = false,
+                }
+                self.notice = Some((msg().thank_failed)(&error.message));
+                None
+            }
+            Action::FavoriteSet(url, favorite, result) => {
+                if self.url.as_ref() != Some(&url) {
+                    return None;
+                }
+                match result {
+                    Ok(()) if favorite => self.notice = Some(msg().favorite_added.to_string()),
+                    Ok(()) => self.notice = Some(msg().favorite_removed.to_string()),
+                    Err(error) => {
+                        set_favorite(self.topic_detail.as_mut()?, !favorite);
+                        self.notice = Some((msg().favorite_failed)(&error.message));
+                    }
+                }
+                None
+            }
+            Action::TopicIgnored(url, result) => {
+                if self.url.as_ref() != Some(&url) {
+                    return None;
+                }
+                match result {
+                    Ok(()) => Some(Action::GoBack),
+                    Err(error) => {
+                        self.notice = Some((msg().ignore_failed)(&error.message));
+                        None
+                    }
+                }
+            }
             Action::Bottom => {
                 self.scroll = self.max_scroll;
-                None
+                self.load_more_if_near_end()
             }
             Action::LineUp(count) => {
                 self.scroll = self.scroll.saturating_sub(count);
@@ -175,9 +851,21 @@ impl Page for DetailPage {
             }
             Action::LineDown(count) => {
                 self.scroll = self.scroll.saturating_add(count).min(self.max_scroll);
-                None
+                self.load_more_if_near_end()
             }
             _ => None,
         }
     }
 }
+
+fn set_favorite(detail: &mut TopicDetail, favorite: bool) {
+    if detail.favorited == favorite {
+        return;
+    }
+    detail.favorited = favorite;
+    detail.favorites = if favorite {
+        detail.favorites + 1
+    } else {
+        detail.favorites.saturating_sub(1)
+    };
+}
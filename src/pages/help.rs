@@ -0,0 +1,180 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Layout},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::{
+    i18n::msg,
+    keymap::{Category, Context, Keymap, Sequence},
+    theme::Theme,
+};
+
+use super::widgets;
+
+struct Entry {
+    category: Category,
+    keys: String,
+    description: &'static str,
+    // The config name, so `load_all` finds its binding too.
+    name: String,
+}
+
+/// The bindings of one page and the global ones, grouped by category and
+/// narrowed down by what the reader types.
+pub struct Help {
+    entries: Vec<Entry>,
+    query: String,
+    scroll: u16,
+    max_scroll: u16,
+    page_height: u16,
+}
+
+impl Help {
+    pub fn new(keymap: &Keymap, context: Context) -> Self {
+        let mut entries: Vec<Entry> = keymap
+            .bindings(context)
+            .into_iter()
+            .map(|(command, sequences)| Entry {
+                category: command.category(),
+                keys: sequences
+                    .iter()
+                    .map(Sequence::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                description: (msg().command)(command),
+                name: command.to_string(),
+            })
+            .collect();
+        entries.sort_by_key(|entry| entry.category);
+        Self {
+            entries,
+            query: String::new(),
+            scroll: 0,
+            max_scroll: 0,
+            page_height: 0,
+        }
+    }
+
+    fn matches(&self) -> impl Iterator<Item = &Entry> {
+        let query = self.query.to_lowercase();
+        self.entries.iter().filter(move |entry| {
+            [
+                entry.keys.as_str(),
+                entry.description,
+                entry.name.as_str(),
+                (msg().category)(entry.category),
+            ]
+            .iter()
+            .any(|text| text.to_lowercase().contains(&query))
+        })
+    }
+
+    /// Returns false once the reader closes the help.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Esc => return false,
+            KeyCode::Char('?') if self.query.is_empty() => return false,
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                self.scroll = 0;
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.scroll = 0;
+            }
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down => self.scroll = self.scroll.saturating_add(1).min(self.max_scroll),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(self.page_height),
+            KeyCode::PageDown => {
+                self.scroll = self
+                    .scroll
+                    .saturating_add(self.page_height)
+                    .min(self.max_scroll)
+            }
+            KeyCode::Home => self.scroll = 0,
+            KeyCode::End => self.scroll = self.max_scroll,
+            _ => {}
+        }
+        true
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, theme: &Theme) {
+        let area = frame.area();
+        let area = widgets::centered(
+            area,
+            area.width.saturating_sub(4).min(72),
+            area.height.saturating_sub(2).min(30),
+        );
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", msg().help_title))
+            .title_bottom(Line::from(Span::styled(
+                format!(" {} ", msg().help_hint),
+                theme.footer,
+            )))
+            .style(theme.border);
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+        let [search, list] =
+            Layout::vertical([Constraint::Length(2), Constraint::Min(0)]).areas(inner);
+
+        let search_line = Line::from(vec![
+            Span::styled(msg().help_search, theme.secondary),
+            Span::styled(self.query.as_str(), theme.text),
+            Span::styled("█", theme.notice),
+        ]);
+        frame.render_widget(Paragraph::new(search_line), search);
+
+        let matches: Vec<&Entry> = self.matches().collect();
+        let width = matches
+            .iter()
+            .map(|entry| entry.keys.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut lines = vec![];
+        let mut category = None;
+        for entry in &matches {
+            if category != Some(entry.category) {
+                if category.is_some() {
+                    lines.push(Line::from(""));
+                }
+                category = Some(entry.category);
+                lines.push(Line::from(Span::styled(
+                    (msg().category)(entry.category),
+                    theme.header,
+                )));
+            }
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:<width$}  ", entry.keys), theme.accent),
+                Span::styled(entry.description, theme.text),
+            ]));
+        }
+        if matches.is_empty() {
+            lines.push(Line::from(Span::styled(msg().help_empty, theme.muted)));
+        }
+
+        self.page_height = list.height.max(1);
+        self.max_scroll = (lines.len() as u16).saturating_sub(list.height);
+        self.scroll = self.scroll.min(self.max_scroll);
+        frame.render_widget(Paragraph::new(lines).scroll((self.scroll, 0)), list);
+    }
+}
+
+#[test]
+fn test_help() {
+    let mut help = Help::new(&Keymap::default(), Context::Detail);
+    assert!(help.matches().any(|entry| entry.keys == "?"));
+    assert!(help.matches().all(|entry| entry.name != "nodes"));
+
+    for c in "load_".chars() {
+        help.handle_key(KeyEvent::from(KeyCode::Char(c)));
+    }
+    let keys: Vec<&str> = help.matches().map(|entry| entry.keys.as_str()).collect();
+    assert_eq!(keys, ["a"]);
+    assert!(help.handle_key(KeyEvent::from(KeyCode::Char('?'))));
+    assert!(!help.handle_key(KeyEvent::from(KeyCode::Esc)));
+}
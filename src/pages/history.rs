@@ -0,0 +1,258 @@
+use std::rc::Rc;
+
+use chrono::DateTime;
+
+use crate::{
+    action::Action,
+    i18n::msg,
+    keymap::{Command, Context, Keymap},
+    store::history::{HistoryEntry, HistoryStore},
+    theme::Theme,
+    time::time_formatting::format_relative_time,
+};
+
+use super::{widgets, Page, PageType};
+use crossterm::event::{Event, KeyCode};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+/// What is waiting for confirmation.
+#[derive(Clone, Copy)]
+enum Pending {
+    Delete,
+    Clear,
+}
+
+/// Topics opened before, most recent first; each reopens where it was left.
+pub struct HistoryPage {
+    store: HistoryStore,
+    state: TableState,
+    confirm: Option<Pending>,
+    notice: Option<String>,
+    keymap: Rc<Keymap>,
+}
+
+impl HistoryPage {
+    pub fn new(keymap: Rc<Keymap>) -> Self {
+        HistoryPage {
+            store: HistoryStore::load(),
+            state: TableState::default().with_selected(0),
+            confirm: None,
+            notice: None,
+            keymap,
+        }
+    }
+
+    fn selected(&self) -> Option<&HistoryEntry> {
+        self.state
+            .selected()
+            .and_then(|index| self.store.entries().get(index))
+    }
+
+    fn save(&mut self) {
+        if let Err(e) = self.store.save() {
+            self.notice = Some((msg().history_save_failed)(&e.to_string()));
+        }
+    }
+}
+
+impl Page for HistoryPage {
+    fn page_type(&self) -> PageType {
+        PageType::History
+    }
+
+    fn context(&self) -> Option<Context> {
+        Some(Context::History)
+    }
+
+    fn captures_input(&self) -> bool {
+        self.confirm.is_some()
+    }
+
+    fn init(&mut self) -> Option<Action> {
+        self.state.select_first();
+        None
+    }
+
+    fn render(&mut self, frame: &mut Frame, theme: &Theme) {
+        let main_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),    // Table
+                Constraint::Length(3), // Footer
+            ])
+            .margin(1)
+            .split(frame.area());
+
+        let header_cells = msg()
+            .history_columns
+            .iter()
+            .map(|h| Span::styled(*h, theme.header));
+        let header = Row::new(header_cells).height(1).bottom_margin(1);
+        let rows: Vec<Row> = self
+            .store
+            .entries()
+            .iter()
+            .map(|entry| {
+                let visited = DateTime::from_timestamp(entry.visited, 0)
+                    .map(format_relative_time)
+                    .unwrap_or_default();
+                Row::new(vec![
+                    Span::styled(entry.title.as_str(), theme.text),
+                    Span::styled(visited, theme.muted),
+                ])
+            })
+            .collect();
+        let notice = self.notice.as_deref().map_or(Line::default(), |notice| {
+            Line::from(Span::styled(format!(" {} ", notice), theme.notice))
+        });
+        let table = Table::new(
+            rows,
+            [Constraint::Percentage(80), Constraint::Percentage(20)],
+        )
+        .header(header)
+        .block(
+            Block::default()
+                .title(format!(
+                    " {} ",
+                    (msg().history_title)(self.store.entries().len())
+                ))
+                .title_alignment(Alignment::Center)
+                .title_bottom(notice)
+                .borders(Borders::ALL)
+                .style(theme.border),
+        )
+        .column_spacing(1)
+        .row_highlight_style(theme.selection);
+        frame.render_stateful_widget(table, main_layout[0], &mut self.state);
+
+        let hints = self.keymap.hints(
+            Context::History,
+            &[
+                (msg().hint.quit, &[Command::Quit]),
+                (msg().hint.help, &[Command::Help]),
+                (msg().hint.back, &[Command::Back]),
+                (msg().hint.scroll, &[Command::Up, Command::Down]),
+                (msg().hint.top, &[Command::Top]),
+                (msg().hint.bottom, &[Command::Bottom]),
+                (msg().hint.resume, &[Command::Open]),
+                (msg().hint.delete, &[Command::Delete]),
+                (msg().hint.clear, &[Command::Clear]),
+                (msg().hint.open_browser, &[Command::OpenBrowser]),
+            ],
+        );
+        let footer = Paragraph::new(Line::from(Span::styled(hints, theme.footer)))
+            .alignment(Alignment::Left)
+            .block(Block::default().borders(Borders::ALL).style(theme.border));
+        frame.render_widget(footer, main_layout[1]);
+
+        match self.confirm {
+            Some(Pending::Delete) => {
+                if let Some(entry) = self.selected() {
+                    widgets::confirm_popup(
+                        frame,
+                        &(msg().delete_history_prompt)(&entry.title),
+                        theme,
+                    );
+                }
+            }
+            Some(Pending::Clear) => {
+                widgets::confirm_popup(frame, msg().clear_history_prompt, theme)
+            }
+            None => {}
+        }
+    }
+
+    fn handle_event(&mut self, event: Event) -> Option<Action> {
+        let Event::Key(key) = event else {
+            return None;
+        };
+        self.confirm?;
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => Some(Action::Confirm),
+            KeyCode::Char('n') | KeyCode::Esc => Some(Action::CancelConfirm),
+            _ => None,
+        }
+    }
+
+    fn handle_command(&mut self, command: Command) -> Option<Action> {
+        match command {
+            Command::Back => Some(Action::GoBack),
+            Command::Top => Some(Action::Top),
+            Command::Bottom => Some(Action::Bottom),
+            Command::Up => Some(Action::PreviousRow),
+            Command::Down => Some(Action::NextRow),
+            Command::Delete => self.selected().map(|_| Action::DeleteHistoryEntry),
+            Command::Clear if !self.store.entries().is_empty() => Some(Action::ClearHistory),
+            Command::OpenBrowser => self
+                .selected()
+                .map(|entry| Action::OpenBrowser(entry.url.clone())),
+            Command::Open => self.selected().map(|entry| Action::ResumeTopic {
+                url: entry.url.clone(),
+                scroll: entry.scroll,
+            }),
+            _ => None,
+        }
+    }
+
+    fn update(&mut self, action: Action) -> Option<Action> {
+        match action {
+            Action::TopicVisited { url, title, scroll } => {
+                self.store.record(&url, &title, scroll);
+                self.save();
+                None
+            }
+            Action::RestoreSession => {
+                let entry = self.store.entries().first()?;
+                Some(Action::ResumeTopic {
+                    url: entry.url.clone(),
+                    scroll: entry.scroll,
+                })
+            }
+            Action::DeleteHistoryEntry => {
+                self.confirm = Some(Pending::Delete);
+                None
+            }
+            Action::ClearHistory => {
+                self.confirm = Some(Pending::Clear);
+                None
+            }
+            Action::CancelConfirm => {
+                self.confirm = None;
+                None
+            }
+            Action::Confirm => {
+                match self.confirm.take()? {
+                    Pending::Delete => {
+                        let index = self.state.selected()?;
+                        self.store.remove(index);
+                    }
+                    Pending::Clear => self.store.clear(),
+                }
+                self.save();
+                None
+            }
+            Action::Top => {
+                self.state.select_first();
+                None
+            }
+            Action::Bottom => {
+                self.state.select_last();
+                None
+            }
+            Action::PreviousRow => {
+                self.state.select_previous();
+                None
+            }
+            Action::NextRow => {
+                self.state.select_next();
+                None
+            }
+            _ => None,
+        }
+    }
+}
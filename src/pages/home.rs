@@ -1,45 +1,182 @@
-use crate::{action::Action, api::Crawler, model::Topic};
+use std::{rc::Rc, time::Duration};
 
-use super::{Page, PageType};
-use crossterm::event::{Event, KeyCode};
+use crate::{
+    action::Action,
+    api::{session::SessionStatus, topic_id, FetchError, FetchResult},
+    checkin,
+    i18n::msg,
+    keymap::{Command, Context, Keymap},
+    model::{CheckIn, Feed, Member, Tab, Topic},
+    store::read::ReadStore,
+    theme::Theme,
+    worker::Worker,
+};
+
+use super::{widgets, Page, PageType};
+use crossterm::event::Event;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
-    style::{palette::tailwind, Color, Modifier, Style, Stylize},
+    style::{Style, Stylize},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Row, Table, TableState},
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState, Tabs},
     Frame,
 };
 
-pub struct TableColors {
-    pub selected_row_style_fg: Color,
+/// The topics of one tab or node, kept around while other feeds are shown.
+pub struct FeedState {
+    pub feed: Feed,
+    pub items: Vec<Topic>,
+    pub state: TableState,
+    loading: bool,
+    loaded: bool,
+    error: Option<FetchError>,
+    // What a node says about itself, shown under its topics.
+    header: String,
+    // Paging through the HTML listing once the RSS window runs out.
+    next_page: u32,
+    has_more: bool,
+    loading_more: bool,
+    more_error: Option<FetchError>,
 }
 
-impl TableColors {
-    const fn new(color: &tailwind::Palette) -> Self {
+impl FeedState {
+    fn new(feed: Feed) -> Self {
+        let has_more = feed.has_listing();
         Self {
-            selected_row_style_fg: color.c400,
+            feed,
+            items: vec![],
+            state: TableState::default(),
+            loading: false,
+            loaded: false,
+            error: None,
+            header: String::new(),
+            next_page: 1,
+            has_more,
+            loading_more: false,
+            more_error: None,
+        }
+    }
+
+    fn at_last_row(&self) -> bool {
+        self.state
+            .selected()
+            .is_some_and(|index| index + 1 >= self.items.len())
+    }
+
+    fn fetch_more(&mut self, worker: &Worker) {
+        if self.loading || self.loading_more || !self.has_more {
+            return;
+        }
+        self.loading_more = true;
+        self.more_error = None;
+        worker.fetch_more_topics(self.feed.clone(), self.next_page);
+    }
+
+    /// Appends topics that are not in the list yet and returns how many were new.
+    fn append(&mut self, topics: Vec<Topic>) -> usize {
+        let before = self.items.len();
+        for topic in topics {
+            let id = topic_id(&topic.link);
+            if !self.items.iter().any(|item| topic_id(&item.link) == id) {
+                self.items.push(topic);
+            }
+        }
+        self.items.len() - before
+    }
+
+    fn status(&self, tick: usize) -> String {
+        if self.loading_more {
+            format!("{} {}", widgets::spinner(tick), msg().loading_more)
+        } else if let Some(error) = &self.more_error {
+            (msg().load_more_failed)(&error.message)
+        } else if self.loaded && !self.has_more && self.feed.has_listing() {
+            msg().no_more.to_string()
+        } else {
+            String::new()
         }
     }
 }
 
 pub struct HomePage {
-    pub items: Vec<Topic>,
-    pub state: TableState,
-    pub colors: TableColors,
-    loading: bool,
-    crawler: Crawler,
+    // One entry per tab in `Tab::TABS` order, followed by at most one opened node.
+    pub feeds: Vec<FeedState>,
+    selected: usize,
+    session: Option<FetchResult<SessionStatus>>,
+    // The owner of the configured API token, or why it was rejected.
+    token_owner: Option<FetchResult<Member>>,
+    // Polled in the background once the session is known to work.
+    unread: Option<u32>,
+    polling_unread: bool,
+    unread_poll_interval: Duration,
+    // Auto check-in waits for the session check, then reports here.
+    check_in_wanted: bool,
+    check_in: Option<FetchResult<CheckIn>>,
+    read: ReadStore,
+    read_error: Option<String>,
+    // Outcome of the last bookmark added from here.
+    notice: Option<String>,
+    tick: usize,
+    keymap: Rc<Keymap>,
+    worker: Worker,
 }
 
 impl HomePage {
-    pub fn new() -> Self {
+    pub fn new(worker: Worker, keymap: Rc<Keymap>, unread_poll_interval: Duration) -> Self {
         HomePage {
-            items: vec![],
-            state: TableState::default(),
-            loading: true,
-            colors: TableColors::new(&tailwind::BLUE),
-            crawler: Crawler::new(),
+            feeds: Tab::TABS
+                .iter()
+                .map(|tab| FeedState::new(Feed::Tab(*tab)))
+                .collect(),
+            selected: Tab::All.index(),
+            session: None,
+            token_owner: None,
+            unread: None,
+            polling_unread: false,
+            unread_poll_interval,
+            check_in_wanted: false,
+            check_in: None,
+            read: ReadStore::load(),
+            read_error: None,
+            notice: None,
+            tick: 0,
+            keymap,
+            worker,
+        }
+    }
+
+    fn feed(&self) -> &FeedState {
+        &self.feeds[self.selected]
+    }
+
+    fn feed_mut(&mut self) -> &mut FeedState {
+        &mut self.feeds[self.selected]
+    }
+
+    fn logged_in(&self) -> bool {
+        matches!(self.session, Some(Ok(SessionStatus::LoggedIn(_))))
+    }
+
+    fn select_feed(&mut self, index: usize) -> Option<Action> {
+        self.selected = index;
+        let feed = self.feed();
+        if feed.loaded || feed.loading {
+            None
+        } else {
+            Some(Action::FetchTopics)
         }
     }
+
+    fn open_node(&mut self, name: String, title: String, header: String) -> Option<Action> {
+        let feed = Feed::Node { name, title };
+        if let Some(index) = self.feeds.iter().position(|f| f.feed == feed) {
+            return self.select_feed(index);
+        }
+        self.feeds.truncate(Tab::TABS.len());
+        let mut state = FeedState::new(feed);
+        state.header = header;
+        self.feeds.push(state);
+        self.select_feed(self.feeds.len() - 1)
+    }
 }
 
 impl Page for HomePage {
@@ -47,15 +184,24 @@ impl Page for HomePage {
         PageType::Home
     }
 
+    fn context(&self) -> Option<Context> {
+        Some(Context::Home)
+    }
+
     fn init(&mut self) -> Option<Action> {
+        self.worker.check_session();
+        if self.worker.has_token() {
+            self.worker.check_token();
+        }
         Some(Action::FetchTopics)
     }
 
-    fn render(&mut self, frame: &mut Frame) {
+    fn render(&mut self, frame: &mut Frame, theme: &Theme) {
         let main_layout: std::rc::Rc<[ratatui::prelude::Rect]> = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(3), // Header
+                Constraint::Length(1), // Tabs
                 Constraint::Min(0),    // Table
                 Constraint::Length(3), // Footer
             ])
@@ -63,41 +209,122 @@ impl Page for HomePage {
             .split(frame.area());
 
         // Render header with centered text
-        let title = Line::from(vec![
-            Span::styled("欢迎来到 ", Style::default().fg(Color::Gray)),
-            Span::styled(
-                "V2EX",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            ),
+        let mut title = Line::from(vec![
+            Span::styled(msg().welcome, theme.secondary),
+            Span::styled("V2EX", theme.title),
         ]);
-        let header = Paragraph::new(title).alignment(Alignment::Center).block(
-            Block::default()
-                .borders(Borders::ALL)
-                .style(Style::default().fg(Color::Cyan)),
-        );
+        match &self.session {
+            Some(Ok(SessionStatus::LoggedIn(username))) => {
+                title.push_span(Span::styled(format!(" · @{}", username), theme.accent));
+            }
+            Some(Ok(SessionStatus::Expired)) => {
+                title.push_span(Span::styled(
+                    format!(" · {}", msg().session_expired),
+                    theme.error,
+                ));
+            }
+            Some(Err(error)) => {
+                title.push_span(Span::styled(
+                    format!(" · {}", (msg().session_check_failed)(&error.message)),
+                    theme.error,
+                ));
+            }
+            Some(Ok(SessionStatus::Anonymous)) | None => {}
+        }
+        match &self.token_owner {
+            Some(Ok(member)) if !self.logged_in() => {
+                title.push_span(Span::styled(
+                    format!(" · @{}", member.username),
+                    theme.accent,
+                ));
+            }
+            Some(Err(error)) => {
+                title.push_span(Span::styled(format!(" · {}", error.message), theme.error));
+            }
+            _ => {}
+        }
+        match self.unread {
+            Some(0) => {
+                title.push_span(Span::styled(format!(" · {}", msg().no_unread), theme.muted))
+            }
+            Some(unread) => title.push_span(Span::styled(
+                format!(" · {}", (msg().unread)(unread)),
+                theme.notice.bold(),
+            )),
+            None => {}
+        }
+        if let Some(notice) = &self.notice {
+            title.push_span(Span::styled(format!(" · {}", notice), theme.notice));
+        }
+        if let Some(error) = &self.read_error {
+            title.push_span(Span::styled(
+                format!(" · {}", (msg().read_save_failed)(error)),
+                theme.error,
+            ));
+        }
+        match &self.check_in {
+            Some(Ok(check_in)) => title.push_span(Span::styled(
+                format!(" · {}", checkin::describe(check_in)),
+                theme.muted,
+            )),
+            Some(Err(error)) => {
+                title.push_span(Span::styled(
+                    format!(" · {}", (msg().check_in_failed)(&error.message)),
+                    theme.error,
+                ));
+            }
+            None => {}
+        }
+        if let Some(rate_limit) = self.worker.rate_limit() {
+            let quota = match rate_limit.limit {
+                Some(limit) => format!(" · {} {}/{}", msg().api_quota, rate_limit.remaining, limit),
+                None => format!(" · {} {}", msg().api_quota, rate_limit.remaining),
+            };
+            title.push_span(Span::styled(quota, theme.muted));
+        }
+        let header = Paragraph::new(title)
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL).style(theme.border));
         frame.render_widget(header, main_layout[0]);
 
+        let tabs = Tabs::new(self.feeds.iter().map(|f| f.feed.title()))
+            .select(self.selected)
+            .style(theme.tab)
+            .highlight_style(theme.tab_selected);
+        frame.render_widget(tabs, main_layout[1]);
+
         // Create table with styled mock data
-        let header_cells = ["话题", "楼主", "评论数", "活跃时间"]
+        let header_cells = msg()
+            .topic_columns
             .iter()
-            .map(|h| h.bold().yellow());
+            .map(|h| Span::styled(*h, theme.header));
         let header = Row::new(header_cells).height(1).bottom_margin(1);
-        let selected_row_style = Style::default()
-            .add_modifier(Modifier::REVERSED)
-            .fg(self.colors.selected_row_style_fg);
 
-        // init rows from self.items
-        let rows: Vec<Row> = self
+        // init rows from the current feed's items
+        let feed = &mut self.feeds[self.selected];
+        let read = &self.read;
+        let rows: Vec<Row> = feed
             .items
             .iter()
             .map(|item| {
+                let mark = read.get(&item.link);
+                let title = match mark {
+                    Some(_) => Span::styled(item.title.as_str(), theme.muted),
+                    None => Span::styled(item.title.as_str(), theme.text),
+                };
+                let mut comment = Line::from(Span::styled(item.comment.as_str(), theme.count));
+                let new_replies = mark.map_or(0, |mark| mark.new_replies(item.reply_count()));
+                if new_replies > 0 {
+                    comment.push_span(Span::styled(
+                        (msg().new_replies)(new_replies),
+                        theme.notice.bold(),
+                    ));
+                }
                 Row::new(vec![
-                    item.title.as_str().white(),
-                    item.author.as_str().green(),
-                    item.comment.as_str().cyan(),
-                    item.updated.as_str().dark_gray(),
+                    Line::from(title),
+                    Line::from(Span::styled(item.author.as_str(), theme.accent)),
+                    comment,
+                    Line::from(Span::styled(item.updated.as_str(), theme.muted)),
                 ])
             })
             .collect();
@@ -114,55 +341,96 @@ impl Page for HomePage {
         .header(header)
         .block(
             Block::default()
-                .title(" 全部话题 ")
+                .title(format!(" {} ", feed.feed.title()))
+                .title_bottom(
+                    Line::from(if feed.header.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" {} ", feed.header)
+                    })
+                    .left_aligned(),
+                )
+                .title_bottom(Line::from(format!(" {} ", feed.status(self.tick))).right_aligned())
                 .title_alignment(Alignment::Center)
                 .borders(Borders::ALL)
-                .style(Style::default().fg(Color::Cyan)),
+                .style(theme.border),
         )
         .column_spacing(1)
         .style(Style::default())
-        .row_highlight_style(selected_row_style);
+        .row_highlight_style(theme.selection);
 
-        if self.loading {
-            let loading_text = Line::from(vec!["Loading...".yellow()]);
-            let loading = Paragraph::new(loading_text)
+        if feed.loading {
+            let loading = Paragraph::new(widgets::loading_line(self.tick, theme))
                 .alignment(Alignment::Center)
-                .block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .style(Style::default().fg(Color::Cyan)),
-                );
-            frame.render_widget(loading, main_layout[1]);
+                .block(Block::default().borders(Borders::ALL).style(theme.border));
+            frame.render_widget(loading, main_layout[2]);
+        } else if let Some(error) = &feed.error {
+            let hint = self.keymap.hints(
+                Context::Home,
+                &[
+                    (msg().hint.retry, &[Command::Reload]),
+                    (msg().hint.back, &[Command::Back]),
+                ],
+            );
+            frame.render_widget(
+                widgets::error_paragraph(error, &hint, theme),
+                main_layout[2],
+            );
         } else {
-            frame.render_stateful_widget(table, main_layout[1], &mut self.state);
+            frame.render_stateful_widget(table, main_layout[2], &mut feed.state);
         }
         // Render footer with help text
-        let footer_text = Line::from(vec![
-            "退出：q｜滚动：↑↓jk｜移到顶部：t｜移到底部：b｜查看：Enter"
-                .cyan()
-                .bold(),
-        ]);
+        let hints = self.keymap.hints(
+            Context::Home,
+            &[
+                (msg().hint.quit, &[Command::Quit]),
+                (msg().hint.help, &[Command::Help]),
+                (msg().hint.scroll, &[Command::Up, Command::Down]),
+                (msg().hint.switch, &[Command::PreviousTab, Command::NextTab]),
+                (msg().hint.open, &[Command::Open]),
+            ],
+        );
+        let footer_text = Line::from(Span::styled(hints, theme.footer));
         let footer = Paragraph::new(footer_text)
             .alignment(Alignment::Left)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .style(Style::default().fg(Color::Cyan)),
-            );
-        frame.render_widget(footer, main_layout[2]);
-    }
-
-    fn handle_event(&mut self, event: Event) -> Option<Action> {
-        match event {
-            Event::Key(key) => match key.code {
-                KeyCode::Char('r') => Some(Action::Reload),
-                KeyCode::Char('t') => Some(Action::Top),
-                KeyCode::Char('b') => Some(Action::Bottom),
-                KeyCode::Up | KeyCode::Char('k') => Some(Action::PreviousRow),
-                KeyCode::Down | KeyCode::Char('j') => Some(Action::NextRow),
-                KeyCode::Enter => Some(Action::Enter),
-                _ => None,
-            },
+            .block(Block::default().borders(Borders::ALL).style(theme.border));
+        frame.render_widget(footer, main_layout[3]);
+    }
+
+    fn handle_event(&mut self, _event: Event) -> Option<Action> {
+        None
+    }
+
+    fn handle_command(&mut self, command: Command) -> Option<Action> {
+        if let Some(index) = command.tab_index() {
+            return Some(Action::SelectTab(Tab::TABS[index]));
+        }
+        let feed = self.feed();
+        let selected = feed
+            .state
+            .selected()
+            .and_then(|index| feed.items.get(index));
+        match command {
+            Command::Reload => Some(Action::Reload),
+            Command::Top => Some(Action::Top),
+            Command::Bottom => Some(Action::Bottom),
+            Command::Up => Some(Action::PreviousRow),
+            Command::Down => Some(Action::NextRow),
+            Command::PreviousTab => Some(Action::PreviousTab),
+            Command::NextTab => Some(Action::NextTab),
+            Command::Nodes => Some(Action::GoNodes),
+            Command::NewTopic => Some(Action::GoNewTopic),
+            Command::Notifications => Some(Action::GoNotifications),
+            Command::Bookmarks => Some(Action::GoBookmarks),
+            Command::History => Some(Action::GoHistory),
+            Command::Bookmark => selected.map(|item| {
+                let link = item.link.split('#').next().unwrap_or(&item.link);
+                Action::AddBookmark(link.to_string())
+            }),
+            Command::Author => selected.map(|item| Action::OpenMember(item.author.clone())),
+            Command::Open if !feed.loading && feed.error.is_none() => Some(Action::Enter),
+            Command::Back if feed.error.is_some() => Some(Action::DismissError),
+            Command::Back => Some(Action::GoBack),
             _ => None,
         }
     }
@@ -170,36 +438,188 @@ impl Page for HomePage {
     fn update(&mut self, action: Action) -> Option<Action> {
         match action {
             Action::Reload => {
-                self.loading = true;
-                self.state.select_first();
+                let feed = self.feed_mut();
+                feed.loading = true;
+                feed.state.select_first();
                 Some(Action::FetchTopics)
             }
             Action::FetchTopics => {
-                self.loading = false;
-                self.state.select_first();
-                self.items = self.crawler.fetch_topics().unwrap();
+                self.feed_mut().loading = true;
+                self.worker.fetch_topics(self.feed().feed.clone());
+                None
+            }
+            Action::TopicIgnored(url, Ok(())) => {
+                let id = topic_id(&url);
+                for feed in &mut self.feeds {
+                    feed.items.retain(|topic| topic_id(&topic.link) != id);
+                }
+                None
+            }
+            Action::TopicsLoaded(feed, result) => {
+                // The node may have been replaced by another one in the meantime.
+                let feed = self.feeds.iter_mut().find(|f| f.feed == feed)?;
+                feed.loading = false;
+                feed.loaded = true;
+                match result {
+                    Ok(items) => {
+                        feed.error = None;
+                        feed.state.select_first();
+                        feed.items = items;
+                        feed.next_page = 1;
+                        feed.has_more = feed.feed.has_listing();
+                        feed.loading_more = false;
+                        feed.more_error = None;
+                    }
+                    Err(error) => feed.error = Some(error),
+                }
+                None
+            }
+            Action::MoreTopicsLoaded(feed, page, result) => {
+                let feed = self.feeds.iter_mut().find(|f| f.feed == feed)?;
+                if !feed.loading_more || page != feed.next_page {
+                    return None;
+                }
+                feed.loading_more = false;
+                match result {
+                    Ok(topics) if topics.is_empty() => feed.has_more = false,
+                    Ok(topics) => {
+                        feed.next_page += 1;
+                        // Early listing pages overlap the RSS window; keep going
+                        // until something new turns up.
+                        if feed.append(topics) == 0 {
+                            feed.fetch_more(&self.worker);
+                        }
+                    }
+                    Err(error) => feed.more_error = Some(error),
+                }
+                None
+            }
+            Action::SessionChecked(result) => {
+                self.session = Some(result);
+                if self.logged_in() && !self.polling_unread {
+                    self.polling_unread = true;
+                    self.worker.poll_unread(self.unread_poll_interval);
+                }
+                if self.logged_in() && self.check_in_wanted {
+                    self.check_in_wanted = false;
+                    self.worker.check_in();
+                }
+                None
+            }
+            Action::CheckIn => {
+                if self.logged_in() {
+                    self.worker.check_in();
+                } else {
+                    self.check_in_wanted = true;
+                }
+                None
+            }
+            Action::TopicRead {
+                url,
+                replies,
+                floor,
+            } => {
+                if self.read.mark(&url, replies, floor) {
+                    self.read_error = self.read.save().err().map(|e| e.to_string());
+                }
+                None
+            }
+            Action::BookmarkSaved(result) => {
+                self.notice = Some(match result {
+                    Ok(title) => (msg().bookmark_added)(&title),
+                    Err(error) => error,
+                });
+                None
+            }
+            Action::CheckedIn(result) => {
+                self.check_in = Some(result);
+                None
+            }
+            Action::CheckUnread => {
+                if self.logged_in() {
+                    self.worker.check_unread();
+                }
+                None
+            }
+            Action::UnreadLoaded(result) => {
+                // A failed check keeps showing the last known count.
+                if let Ok(unread) = result {
+                    self.unread = Some(unread);
+                }
+                None
+            }
+            Action::TokenChecked(result) => {
+                self.token_owner = Some(result);
+                None
+            }
+            Action::DismissError => {
+                self.feed_mut().error = None;
+                None
+            }
+            Action::PreviousTab => {
+                let index = (self.selected + self.feeds.len() - 1) % self.feeds.len();
+                self.select_feed(index)
+            }
+            Action::NextTab => {
+                let index = (self.selected + 1) % self.feeds.len();
+                self.select_feed(index)
+            }
+            Action::SelectTab(tab) => self.select_feed(tab.index()),
+            Action::OpenNode {
+                name,
+                title,
+                header,
+            } => self.open_node(name, title, header),
+            Action::FetchNode(name) => {
+                self.worker.fetch_node(name);
+                None
+            }
+            // A node the API does not know may still have a feed, so it opens
+            // under its name.
+            Action::NodeLoaded(name, result) => match result {
+                Ok(node) => self.open_node(node.name, node.title, node.header),
+                Err(_) => self.open_node(name.clone(), name, String::new()),
+            },
+            Action::Tick => {
+                self.tick = self.tick.wrapping_add(1);
                 None
             }
             Action::Top => {
-                self.state.select_first();
+                self.feed_mut().state.select_first();
                 None
             }
             Action::Bottom => {
-                self.state.select_last();
+                let feed = &mut self.feeds[self.selected];
+                feed.state.select_last();
+                feed.fetch_more(&self.worker);
                 None
             }
             Action::PreviousRow => {
-                self.state.select_previous();
+                self.feed_mut().state.select_previous();
                 None
             }
             Action::NextRow => {
-                self.state.select_next();
+                let feed = &mut self.feeds[self.selected];
+                feed.state.select_next();
+                if feed.at_last_row() {
+                    feed.fetch_more(&self.worker);
+                }
                 None
             }
             Action::Enter => {
-                if let Some(index) = self.state.selected() {
-                    if let Some(item) = self.items.get(index) {
-                        return Some(Action::FetchTopicDetail(item.link.clone()));
+                let feed = self.feed();
+                if let Some(index) = feed.state.selected() {
+                    if let Some(item) = feed.items.get(index) {
+                        // Feed links point at the latest floor; open the topic from the
+                        // top, or from the first floor we have not read yet.
+                        let link = item.link.split('#').next().unwrap_or(&item.link);
+                        let link = match self.read.get(link) {
+                            Some(mark) if mark.new_replies(item.reply_count()) > 0 => {
+                                format!("{}#reply{}", link, mark.floor + 1)
+                            }
+                            _ => link.to_string(),
+                        };
+                        return Some(Action::OpenTopic(link));
                     }
                 }
                 None
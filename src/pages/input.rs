@@ -0,0 +1,155 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Position, Rect},
+    text::Line,
+    widgets::{Block, Paragraph},
+    Frame,
+};
+
+/// A minimal multi-line text input; the cursor column counts chars, not bytes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextInput {
+    lines: Vec<String>,
+    row: usize,
+    col: usize,
+}
+
+impl Default for TextInput {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl TextInput {
+    /// Starts with `text` and the cursor at its end.
+    pub fn new(text: &str) -> Self {
+        let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        let row = lines.len() - 1;
+        let col = lines[row].chars().count();
+        Self { lines, row, col }
+    }
+
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    pub fn is_blank(&self) -> bool {
+        self.lines.iter().all(|line| line.trim().is_empty())
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return;
+        }
+        match key.code {
+            KeyCode::Char(c) => {
+                let index = self.byte_index();
+                self.lines[self.row].insert(index, c);
+                self.col += 1;
+            }
+            KeyCode::Enter => {
+                let index = self.byte_index();
+                let rest = self.lines[self.row].split_off(index);
+                self.row += 1;
+                self.lines.insert(self.row, rest);
+                self.col = 0;
+            }
+            KeyCode::Backspace if self.col > 0 => {
+                self.col -= 1;
+                let index = self.byte_index();
+                self.lines[self.row].remove(index);
+            }
+            KeyCode::Backspace if self.row > 0 => {
+                let line = self.lines.remove(self.row);
+                self.row -= 1;
+                self.col = self.lines[self.row].chars().count();
+                self.lines[self.row].push_str(&line);
+            }
+            KeyCode::Delete if self.col < self.line_len() => {
+                let index = self.byte_index();
+                self.lines[self.row].remove(index);
+            }
+            KeyCode::Delete if self.row + 1 < self.lines.len() => {
+                let line = self.lines.remove(self.row + 1);
+                self.lines[self.row].push_str(&line);
+            }
+            KeyCode::Left if self.col > 0 => self.col -= 1,
+            KeyCode::Left if self.row > 0 => {
+                self.row -= 1;
+                self.col = self.line_len();
+            }
+            KeyCode::Right if self.col < self.line_len() => self.col += 1,
+            KeyCode::Right if self.row + 1 < self.lines.len() => {
+                self.row += 1;
+                self.col = 0;
+            }
+            KeyCode::Up if self.row > 0 => {
+                self.row -= 1;
+                self.col = self.col.min(self.line_len());
+            }
+            KeyCode::Down if self.row + 1 < self.lines.len() => {
+                self.row += 1;
+                self.col = self.col.min(self.line_len());
+            }
+            KeyCode::Home => self.col = 0,
+            KeyCode::End => self.col = self.line_len(),
+            _ => {}
+        }
+    }
+
+    /// Draws the text inside `block`, scrolled so the cursor stays visible, and
+    /// places the terminal cursor there when `focused`.
+    pub fn render(&self, frame: &mut Frame, area: Rect, block: Block, focused: bool) {
+        let inner = block.inner(area);
+        let cursor_x = Line::from(&self.lines[self.row][..self.byte_index()]).width() as u16;
+        let scroll_y = (self.row as u16).saturating_sub(inner.height.saturating_sub(1));
+        let scroll_x = cursor_x.saturating_sub(inner.width.saturating_sub(1));
+
+        let lines: Vec<Line> = self
+            .lines
+            .iter()
+            .map(|line| Line::from(line.as_str()))
+            .collect();
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .scroll((scroll_y, scroll_x));
+        frame.render_widget(paragraph, area);
+        if !focused {
+            return;
+        }
+        frame.set_cursor_position(Position::new(
+            inner.x + cursor_x - scroll_x,
+            inner.y + self.row as u16 - scroll_y,
+        ));
+    }
+
+    fn line_len(&self) -> usize {
+        self.lines[self.row].chars().count()
+    }
+
+    fn byte_index(&self) -> usize {
+        self.lines[self.row]
+            .char_indices()
+            .nth(self.col)
+            .map_or(self.lines[self.row].len(), |(index, _)| index)
+    }
+}
+
+#[test]
+fn test_text_input() {
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+    let mut input = TextInput::new("你好");
+    input.handle_key(key(KeyCode::Left));
+    input.handle_key(key(KeyCode::Enter));
+    input.handle_key(key(KeyCode::Char('!')));
+    assert_eq!(input.text(), "你\n!好");
+
+    input.handle_key(key(KeyCode::Backspace));
+    input.handle_key(key(KeyCode::Backspace));
+    assert_eq!(input.text(), "你好");
+    assert!(!input.is_blank());
+    assert!(TextInput::new(" \n ").is_blank());
+}
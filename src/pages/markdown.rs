@@ -0,0 +1,123 @@
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+use crate::theme::Theme;
+
+/// Renders the Markdown people actually write on V2EX (headings, quotes, lists,
+/// fenced code, rules, `code`, **bold** and links) into styled lines. Anything
+/// else is shown as written.
+pub fn render(text: &str, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines = vec![];
+    let mut in_code = false;
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", line),
+                theme.accent,
+            )));
+            continue;
+        }
+
+        let hashes = trimmed.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&hashes) && trimmed[hashes..].starts_with(' ') {
+            lines.push(Line::from(Span::styled(
+                trimmed[hashes..].trim().to_string(),
+                theme.header,
+            )));
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            let mut spans = vec![Span::styled("│ ", theme.muted)];
+            spans.extend(inline(quote.trim_start(), theme.secondary, theme));
+            lines.push(Line::from(spans));
+        } else if ["---", "***", "___"].contains(&trimmed.trim_end()) {
+            lines.push(Line::from(Span::styled("────────────────", theme.muted)));
+        } else if let Some(item) = ["- ", "* ", "+ "]
+            .iter()
+            .find_map(|bullet| trimmed.strip_prefix(bullet))
+        {
+            let indent = &line[..line.len() - trimmed.len()];
+            let mut spans = vec![Span::raw(format!("{}• ", indent))];
+            spans.extend(inline(item, Style::default(), theme));
+            lines.push(Line::from(spans));
+        } else {
+            lines.push(Line::from(inline(line, Style::default(), theme)));
+        }
+    }
+    lines
+}
+
+/// Splits a line into spans for `code`, **bold** and [links](url).
+fn inline(text: &str, base: Style, theme: &Theme) -> Vec<Span<'static>> {
+    let mut spans = vec![];
+    let mut plain = String::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let styled = if let Some(code) = enclosed(rest, "`", "`") {
+            Some((code, base.patch(theme.accent), code.len() + 2))
+        } else if let Some(bold) = enclosed(rest, "**", "**") {
+            Some((bold, base.add_modifier(Modifier::BOLD), bold.len() + 4))
+        } else if let Some(label) = enclosed(rest, "[", "](") {
+            let after = &rest[label.len() + 3..];
+            after.find(')').map(|end| {
+                let link = base.patch(theme.link);
+                (label, link, label.len() + 3 + end + 1)
+            })
+        } else {
+            None
+        };
+        match styled {
+            Some((content, style, consumed)) => {
+                if !plain.is_empty() {
+                    spans.push(Span::styled(std::mem::take(&mut plain), base));
+                }
+                spans.push(Span::styled(content.to_string(), style));
+                rest = &rest[consumed..];
+            }
+            None => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    if !plain.is_empty() || spans.is_empty() {
+        spans.push(Span::styled(plain, base));
+    }
+    spans
+}
+
+/// The non-empty text between `open` at the start of `text` and the next `close`.
+fn enclosed<'a>(text: &'a str, open: &str, close: &str) -> Option<&'a str> {
+    let inner = text.strip_prefix(open)?;
+    let end = inner.find(close)?;
+    (end > 0).then(|| &inner[..end])
+}
+
+#[test]
+fn test_render_markdown() {
+    use ratatui::style::Color;
+
+    let lines = render("# 标题\n> 引用\n- 项目 `code`\n```\n# not a heading\n```\n看 [这里](https://v2ex.com) **好**", &Theme::default());
+    let text: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+    assert_eq!(
+        text,
+        [
+            "标题",
+            "│ 引用",
+            "• 项目 code",
+            "  # not a heading",
+            "看 这里 好"
+        ]
+    );
+    assert_eq!(lines[0].spans[0].style.fg, Some(Color::Yellow));
+    assert_eq!(lines[4].spans[1].style.fg, Some(Color::Blue));
+    assert!(lines[4].spans[3]
+        .style
+        .add_modifier
+        .contains(Modifier::BOLD));
+}
@@ -0,0 +1,368 @@
+use std::rc::Rc;
+
+use crate::{
+    action::Action,
+    api::FetchError,
+    i18n::msg,
+    keymap::{Command, Context, Keymap},
+    model::MemberProfile,
+    theme::Theme,
+    worker::Worker,
+};
+
+use super::{widgets, Page, PageType};
+use crossterm::event::Event;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState, Tabs, Wrap},
+    Frame,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Topics,
+    Replies,
+}
+
+/// A member's profile with their recent topics and replies.
+pub struct MemberPage {
+    username: String,
+    profile: Option<MemberProfile>,
+    section: Section,
+    topics_state: TableState,
+    replies_state: TableState,
+    loading: bool,
+    error: Option<FetchError>,
+    tick: usize,
+    keymap: Rc<Keymap>,
+    worker: Worker,
+}
+
+impl MemberPage {
+    pub fn new(worker: Worker, keymap: Rc<Keymap>) -> Self {
+        MemberPage {
+            username: String::new(),
+            profile: None,
+            section: Section::Topics,
+            topics_state: TableState::default(),
+            replies_state: TableState::default(),
+            loading: false,
+            error: None,
+            tick: 0,
+            keymap,
+            worker,
+        }
+    }
+
+    fn state_mut(&mut self) -> &mut TableState {
+        match self.section {
+            Section::Topics => &mut self.topics_state,
+            Section::Replies => &mut self.replies_state,
+        }
+    }
+
+    fn selected_link(&self) -> Option<String> {
+        let profile = self.profile.as_ref()?;
+        match self.section {
+            Section::Topics => self
+                .topics_state
+                .selected()
+                .and_then(|index| profile.topics.get(index))
+                .map(|topic| topic.link.clone()),
+            Section::Replies => self
+                .replies_state
+                .selected()
+                .and_then(|index| profile.replies.get(index))
+                .map(|reply| reply.topic_link.clone()),
+        }
+    }
+
+    fn profile_lines(profile: &MemberProfile, theme: &Theme) -> Vec<Line<'static>> {
+        let member = &profile.member;
+        let mut lines = vec![Line::from(vec![
+            Span::styled(member.username.clone(), theme.title),
+            Span::styled(
+                format!("  {}", (msg().member_since)(member.id, &member.created)),
+                theme.muted,
+            ),
+        ])];
+        if !member.tagline.is_empty() {
+            lines.push(Line::from(Span::styled(member.tagline.clone(), theme.text)));
+        }
+        if !member.bio.is_empty() {
+            lines.push(Line::from(Span::styled(
+                member.bio.clone(),
+                theme.secondary,
+            )));
+        }
+        let links: Vec<Span> = [
+            (msg().website, &member.website),
+            ("GitHub", &member.github),
+            ("Twitter", &member.twitter),
+            (msg().location, &member.location),
+        ]
+        .into_iter()
+        .filter(|(_, value)| !value.is_empty())
+        .flat_map(|(label, value)| {
+            [
+                Span::styled(format!("{}{}", label, msg().colon), theme.secondary),
+                Span::styled(format!("{}  ", value), theme.accent),
+            ]
+        })
+        .collect();
+        if !links.is_empty() {
+            lines.push(Line::from(links));
+        }
+        lines
+    }
+}
+
+impl Page for MemberPage {
+    fn page_type(&self) -> PageType {
+        PageType::Member
+    }
+
+    fn context(&self) -> Option<Context> {
+        Some(Context::Member)
+    }
+
+    fn init(&mut self) -> Option<Action> {
+        None
+    }
+
+    fn render(&mut self, frame: &mut Frame, theme: &Theme) {
+        let main_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(6), // Profile
+                Constraint::Length(1), // Sections
+                Constraint::Min(0),    // List
+                Constraint::Length(3), // Footer
+            ])
+            .margin(1)
+            .split(frame.area());
+
+        if self.loading {
+            let loading = Paragraph::new(widgets::loading_line(self.tick, theme))
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::ALL).style(theme.border));
+            frame.render_widget(loading, main_layout[0]);
+        } else if let Some(error) = &self.error {
+            let hint = self.keymap.hints(
+                Context::Member,
+                &[
+                    (msg().hint.retry, &[Command::Reload]),
+                    (msg().hint.back, &[Command::Back]),
+                ],
+            );
+            frame.render_widget(
+                widgets::error_paragraph(error, &hint, theme),
+                main_layout[0],
+            );
+        } else if let Some(profile) = &self.profile {
+            let profile = Paragraph::new(Self::profile_lines(profile, theme))
+                .wrap(Wrap { trim: true })
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(format!(" {} ", msg().member_title))
+                        .style(theme.border),
+                );
+            frame.render_widget(profile, main_layout[0]);
+        }
+
+        let (topics, replies) = self.profile.as_ref().map_or((0, 0), |profile| {
+            (profile.topics.len(), profile.replies.len())
+        });
+        let tabs = Tabs::new(vec![
+            (msg().recent_topics)(topics),
+            (msg().recent_replies)(replies),
+        ])
+        .select(match self.section {
+            Section::Topics => 0,
+            Section::Replies => 1,
+        })
+        .style(theme.tab)
+        .highlight_style(theme.tab_selected);
+        frame.render_widget(tabs, main_layout[1]);
+
+        let highlight = theme.selection;
+        let block = Block::default().borders(Borders::ALL).style(theme.border);
+        if let Some(profile) = &self.profile {
+            match self.section {
+                Section::Topics => {
+                    let header = Row::new(
+                        msg()
+                            .member_topic_columns
+                            .iter()
+                            .map(|h| Span::styled(*h, theme.header)),
+                    )
+                    .bottom_margin(1);
+                    let rows: Vec<Row> = profile
+                        .topics
+                        .iter()
+                        .map(|topic| {
+                            Row::new(vec![
+                                Span::styled(topic.title.as_str(), theme.text),
+                                Span::styled(topic.comment.as_str(), theme.count),
+                                Span::styled(topic.updated.as_str(), theme.muted),
+                            ])
+                        })
+                        .collect();
+                    let table = Table::new(
+                        rows,
+                        [
+                            Constraint::Percentage(70),
+                            Constraint::Length(8),
+                            Constraint::Percentage(20),
+                        ],
+                    )
+                    .header(header)
+                    .block(block)
+                    .column_spacing(1)
+                    .row_highlight_style(highlight);
+                    frame.render_stateful_widget(table, main_layout[2], &mut self.topics_state);
+                }
+                Section::Replies => {
+                    let header = Row::new(
+                        msg()
+                            .member_reply_columns
+                            .iter()
+                            .map(|h| Span::styled(*h, theme.header)),
+                    )
+                    .bottom_margin(1);
+                    let rows: Vec<Row> = profile
+                        .replies
+                        .iter()
+                        .map(|reply| {
+                            let content = reply.content.lines().next().unwrap_or_default();
+                            Row::new(vec![
+                                Span::styled(reply.topic_title.as_str(), theme.text),
+                                Span::styled(content.to_string(), theme.secondary),
+                                Span::styled(reply.time.as_str(), theme.muted),
+                            ])
+                        })
+                        .collect();
+                    let table = Table::new(
+                        rows,
+                        [
+                            Constraint::Percentage(40),
+                            Constraint::Percentage(45),
+                            Constraint::Percentage(15),
+                        ],
+                    )
+                    .header(header)
+                    .block(block)
+                    .column_spacing(1)
+                    .row_highlight_style(highlight);
+                    frame.render_stateful_widget(table, main_layout[2], &mut self.replies_state);
+                }
+            }
+        } else {
+            frame.render_widget(block, main_layout[2]);
+        }
+
+        let hints = self.keymap.hints(
+            Context::Member,
+            &[
+                (msg().hint.quit, &[Command::Quit]),
+                (msg().hint.help, &[Command::Help]),
+                (msg().hint.back, &[Command::Back]),
+                (msg().hint.reload, &[Command::Reload]),
+                (msg().hint.switch, &[Command::PreviousTab, Command::NextTab]),
+                (msg().hint.scroll, &[Command::Up, Command::Down]),
+                (msg().hint.open, &[Command::Open]),
+                (msg().hint.open_browser, &[Command::OpenBrowser]),
+            ],
+        );
+        let footer = Paragraph::new(Line::from(Span::styled(hints, theme.footer)))
+            .alignment(Alignment::Left)
+            .block(Block::default().borders(Borders::ALL).style(theme.border));
+        frame.render_widget(footer, main_layout[3]);
+    }
+
+    fn handle_event(&mut self, _event: Event) -> Option<Action> {
+        None
+    }
+
+    fn handle_command(&mut self, command: Command) -> Option<Action> {
+        match command {
+            Command::Back => Some(Action::GoBack),
+            Command::Reload => Some(Action::FetchMember(self.username.clone())),
+            Command::OpenBrowser => Some(Action::OpenBrowser(format!(
+                "https://www.v2ex.com/member/{}",
+                self.username
+            ))),
+            Command::Top => Some(Action::Top),
+            Command::Bottom => Some(Action::Bottom),
+            Command::Up => Some(Action::PreviousRow),
+            Command::Down => Some(Action::NextRow),
+            Command::PreviousTab => Some(Action::PreviousTab),
+            Command::NextTab => Some(Action::NextTab),
+            // Reply links keep their `#replyN`, so the topic opens at that floor.
+            Command::Open => self.selected_link().map(Action::OpenTopic),
+            _ => None,
+        }
+    }
+
+    fn update(&mut self, action: Action) -> Option<Action> {
+        match action {
+            Action::FetchMember(username) => {
+                if username != self.username {
+                    self.profile = None;
+                    self.section = Section::Topics;
+                }
+                self.username = username.clone();
+                self.loading = true;
+                self.error = None;
+                self.worker.fetch_member(username);
+                None
+            }
+            Action::MemberLoaded(username, result) => {
+                // A slower answer for a member we already left.
+                if username != self.username {
+                    return None;
+                }
+                self.loading = false;
+                match result {
+                    Ok(profile) => {
+                        self.profile = Some(profile);
+                        self.topics_state.select_first();
+                        self.replies_state.select_first();
+                    }
+                    Err(error) => self.error = Some(error),
+                }
+                None
+            }
+            Action::PreviousTab | Action::NextTab => {
+                self.section = match self.section {
+                    Section::Topics => Section::Replies,
+                    Section::Replies => Section::Topics,
+                };
+                None
+            }
+            Action::Top => {
+                self.state_mut().select_first();
+                None
+            }
+            Action::Bottom => {
+                self.state_mut().select_last();
+                None
+            }
+            Action::PreviousRow => {
+                self.state_mut().select_previous();
+                None
+            }
+            Action::NextRow => {
+                self.state_mut().select_next();
+                None
+            }
+            Action::Tick => {
+                self.tick = self.tick.wrapping_add(1);
+                None
+            }
+            _ => None,
+        }
+    }
+}
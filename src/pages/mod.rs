@@ -1,18 +1,59 @@
-use crate::action::Action;
+use crate::{
+    action::Action,
+    keymap::{Command, Context},
+    theme::Theme,
+};
 
+pub mod bookmarks;
 pub mod detail;
+pub mod help;
+pub mod history;
 pub mod home;
+pub mod input;
+pub mod markdown;
+pub mod member;
+pub mod new_topic;
+pub mod node_list;
+pub mod nodes;
+pub mod notifications;
+pub mod palette;
+pub mod widgets;
 
 pub trait Page {
     fn page_type(&self) -> PageType;
     fn init(&mut self) -> Option<Action>;
-    fn render(&mut self, frame: &mut ratatui::Frame);
+    /// Called when another page covers this one or the app quits; the page
+    /// stays as it is, to be gone back to.
+    fn leave(&mut self) -> Option<Action> {
+        None
+    }
+    /// The keymap section the page's keys come from; without one every key
+    /// goes to `handle_event`.
+    fn context(&self) -> Option<Context> {
+        None
+    }
+    /// Whether the page is taking text input or answering a prompt, so keys
+    /// must go to `handle_event` as they are instead of through the keymap.
+    fn captures_input(&self) -> bool {
+        false
+    }
+    fn render(&mut self, frame: &mut ratatui::Frame, theme: &Theme);
+    /// Input the keymap has no binding for, such as mouse events and text.
     fn handle_event(&mut self, event: crossterm::event::Event) -> Option<Action>;
+    fn handle_command(&mut self, _command: Command) -> Option<Action> {
+        None
+    }
     fn update(&mut self, action: Action) -> Option<Action>;
 }
 
-#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum PageType {
     Home,
     Detail,
+    Nodes,
+    NewTopic,
+    Notifications,
+    Member,
+    Bookmarks,
+    History,
 }
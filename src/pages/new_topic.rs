@@ -0,0 +1,400 @@
+use crate::{
+    action::Action,
+    api::FetchError,
+    i18n::msg,
+    model::{Node, Syntax},
+    theme::Theme,
+    worker::Worker,
+};
+
+use super::{input::TextInput, markdown, node_list::NodeList, widgets, Page, PageType};
+use crossterm::event::{Event, KeyCode, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Node,
+    Title,
+    Body,
+}
+
+/// Writes a new topic: pick a node, fill in title and body, preview, then post.
+/// The draft is kept when leaving the page and only cleared once posted.
+pub struct NewTopicPage {
+    node: Option<Node>,
+    title: TextInput,
+    body: TextInput,
+    syntax: Syntax,
+    focus: Field,
+    // The node picker takes the place of the body while open.
+    picking: bool,
+    nodes: NodeList,
+    nodes_loading: bool,
+    nodes_error: Option<FetchError>,
+    previewing: bool,
+    preview_scroll: u16,
+    sending: bool,
+    error: Option<String>,
+    tick: usize,
+    worker: Worker,
+}
+
+impl NewTopicPage {
+    pub fn new(worker: Worker) -> Self {
+        NewTopicPage {
+            node: None,
+            title: TextInput::default(),
+            body: TextInput::default(),
+            syntax: Syntax::default(),
+            focus: Field::Node,
+            picking: false,
+            nodes: NodeList::default(),
+            nodes_loading: false,
+            nodes_error: None,
+            previewing: false,
+            preview_scroll: 0,
+            sending: false,
+            error: None,
+            tick: 0,
+            worker,
+        }
+    }
+
+    fn editing(&self, field: Field) -> bool {
+        self.focus == field && !self.picking && !self.previewing
+    }
+
+    fn field_style(&self, field: Field, theme: &Theme) -> Style {
+        if self.editing(field) {
+            theme.notice
+        } else {
+            theme.border
+        }
+    }
+
+    fn render_picker(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(area);
+        let filter = Line::from(vec![
+            Span::styled(msg().filter_prompt, theme.secondary),
+            Span::styled(self.nodes.filter(), theme.text),
+            Span::styled("█", theme.notice),
+        ]);
+        frame.render_widget(Paragraph::new(filter), chunks[0]);
+
+        if self.nodes_loading {
+            let loading = Paragraph::new(widgets::loading_line(self.tick, theme))
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::ALL).style(theme.border));
+            frame.render_widget(loading, chunks[1]);
+        } else if let Some(error) = &self.nodes_error {
+            let hint = msg().picker_retry;
+            frame.render_widget(widgets::error_paragraph(error, hint, theme), chunks[1]);
+        } else {
+            self.nodes.render(frame, chunks[1], theme);
+        }
+    }
+
+    fn render_preview(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let mut lines = vec![
+            Line::from(Span::styled(self.title.text(), theme.title)),
+            Line::from(""),
+        ];
+        match self.syntax {
+            Syntax::Markdown => lines.extend(markdown::render(&self.body.text(), theme)),
+            Syntax::Default => lines.extend(
+                self.body
+                    .text()
+                    .lines()
+                    .map(|line| Line::from(line.to_string())),
+            ),
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", (msg().preview_title)(self.syntax.title())))
+            .style(theme.border);
+        let paragraph = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(block);
+        let max_scroll =
+            (paragraph.line_count(area.width.saturating_sub(2)) as u16).saturating_sub(area.height);
+        self.preview_scroll = self.preview_scroll.min(max_scroll);
+        frame.render_widget(paragraph.scroll((self.preview_scroll, 0)), area);
+    }
+}
+
+impl Page for NewTopicPage {
+    fn page_type(&self) -> PageType {
+        PageType::NewTopic
+    }
+
+    fn init(&mut self) -> Option<Action> {
+        None
+    }
+
+    fn captures_input(&self) -> bool {
+        true
+    }
+
+    fn render(&mut self, frame: &mut Frame, theme: &Theme) {
+        let main_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Node
+                Constraint::Length(3), // Title
+                Constraint::Min(0),    // Body
+                Constraint::Length(3), // Footer
+            ])
+            .margin(1)
+            .split(frame.area());
+
+        let node = match &self.node {
+            Some(node) => Line::from(vec![
+                Span::styled(node.title.as_str(), theme.text),
+                Span::styled((msg().node_name)(&node.name), theme.accent),
+            ]),
+            None => Line::from(Span::styled(msg().pick_node, theme.muted)),
+        };
+        let node = Paragraph::new(node).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} ", msg().node_field))
+                .style(self.field_style(Field::Node, theme)),
+        );
+        frame.render_widget(node, main_layout[0]);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", msg().title_field))
+            .style(self.field_style(Field::Title, theme));
+        self.title
+            .render(frame, main_layout[1], block, self.editing(Field::Title));
+
+        if self.picking {
+            self.render_picker(frame, main_layout[2], theme);
+        } else if self.previewing {
+            self.render_preview(frame, main_layout[2], theme);
+        } else {
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} ", (msg().body_field)(self.syntax.title())))
+                .style(self.field_style(Field::Body, theme));
+            self.body
+                .render(frame, main_layout[2], block, self.editing(Field::Body));
+        }
+
+        let footer_text = if self.picking {
+            msg().picker_hint
+        } else if self.previewing {
+            msg().preview_hint
+        } else {
+            msg().editor_hint
+        };
+        let status = if self.sending {
+            Span::styled(
+                format!(" {} {} ", widgets::spinner(self.tick), msg().posting),
+                theme.notice,
+            )
+        } else if let Some(error) = &self.error {
+            Span::styled(format!(" {} ", error), theme.error)
+        } else {
+            "".into()
+        };
+        let footer = Paragraph::new(Line::from(Span::styled(footer_text, theme.footer)))
+            .alignment(Alignment::Left)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title_bottom(Line::from(status))
+                    .style(theme.border),
+            );
+        frame.render_widget(footer, main_layout[3]);
+    }
+
+    fn handle_event(&mut self, event: Event) -> Option<Action> {
+        let Event::Key(key) = event else {
+            return None;
+        };
+        if self.sending {
+            return None;
+        }
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        if self.picking {
+            return match key.code {
+                KeyCode::Esc => Some(Action::EndFilter),
+                KeyCode::Enter => Some(Action::PickNode),
+                KeyCode::Up => Some(Action::PreviousRow),
+                KeyCode::Down => Some(Action::NextRow),
+                KeyCode::Backspace => Some(Action::FilterBackspace),
+                KeyCode::Char(c) if !ctrl => Some(Action::FilterInput(c)),
+                _ => None,
+            };
+        }
+        if self.previewing {
+            return match key.code {
+                KeyCode::Char('s') if ctrl => Some(Action::SubmitTopic),
+                KeyCode::Char('p') if ctrl => Some(Action::TogglePreview),
+                KeyCode::Esc => Some(Action::TogglePreview),
+                KeyCode::Up => Some(Action::LineUp(1)),
+                KeyCode::Down => Some(Action::LineDown(1)),
+                _ => None,
+            };
+        }
+        match key.code {
+            KeyCode::Esc => Some(Action::GoBack),
+            KeyCode::Tab => Some(Action::NextField),
+            KeyCode::BackTab => Some(Action::PreviousField),
+            KeyCode::Char('s' | 'p') if ctrl => Some(Action::TogglePreview),
+            KeyCode::Char('t') if ctrl => Some(Action::ToggleSyntax),
+            KeyCode::Char('e') if ctrl => Some(Action::OpenEditor(self.body.text())),
+            _ => match self.focus {
+                Field::Node if key.code == KeyCode::Enter => Some(Action::StartFilter),
+                Field::Node => None,
+                Field::Title if key.code == KeyCode::Enter => Some(Action::NextField),
+                _ => Some(Action::ComposerInput(key)),
+            },
+        }
+    }
+
+    fn update(&mut self, action: Action) -> Option<Action> {
+        match action {
+            Action::NextField | Action::PreviousField => {
+                let fields = [Field::Node, Field::Title, Field::Body];
+                let index = fields.iter().position(|f| *f == self.focus).unwrap_or(0);
+                let step = if action == Action::NextField { 1 } else { 2 };
+                self.focus = fields[(index + step) % fields.len()];
+                None
+            }
+            Action::StartFilter => {
+                self.picking = true;
+                self.nodes.clear_filter();
+                if self.nodes.is_empty() && !self.nodes_loading {
+                    self.nodes_loading = true;
+                    self.nodes_error = None;
+                    self.worker.fetch_nodes();
+                }
+                None
+            }
+            Action::EndFilter => {
+                self.picking = false;
+                None
+            }
+            Action::NodesLoaded(result) => {
+                self.nodes_loading = false;
+                match result {
+                    Ok(nodes) => self.nodes.set_nodes(nodes),
+                    Err(error) => self.nodes_error = Some(error),
+                }
+                None
+            }
+            Action::FilterInput(c) => {
+                self.nodes.push_filter(c);
+                None
+            }
+            Action::FilterBackspace => {
+                self.nodes.pop_filter();
+                None
+            }
+            Action::PreviousRow => {
+                self.nodes.state.select_previous();
+                None
+            }
+            Action::NextRow => {
+                self.nodes.state.select_next();
+                None
+            }
+            Action::PickNode => {
+                self.node = Some(self.nodes.selected()?.clone());
+                self.picking = false;
+                self.focus = Field::Title;
+                None
+            }
+            Action::ComposerInput(key) => {
+                match self.focus {
+                    Field::Title => self.title.handle_key(key),
+                    Field::Body => self.body.handle_key(key),
+                    Field::Node => {}
+                }
+                None
+            }
+            Action::EditorClosed(result) => {
+                match result {
+                    Ok(text) => {
+                        self.body = TextInput::new(&text);
+                        self.focus = Field::Body;
+                    }
+                    Err(error) => self.error = Some(error),
+                }
+                None
+            }
+            Action::ToggleSyntax => {
+                self.syntax = match self.syntax {
+                    Syntax::Default => Syntax::Markdown,
+                    Syntax::Markdown => Syntax::Default,
+                };
+                None
+            }
+            Action::TogglePreview => {
+                self.previewing = !self.previewing;
+                self.preview_scroll = 0;
+                None
+            }
+            Action::LineUp(count) => {
+                self.preview_scroll = self.preview_scroll.saturating_sub(count);
+                None
+            }
+            Action::LineDown(count) => {
+                self.preview_scroll = self.preview_scroll.saturating_add(count);
+                None
+            }
+            Action::SubmitTopic => {
+                let Some(node) = &self.node else {
+                    self.error = Some(msg().node_missing.to_string());
+                    return None;
+                };
+                if self.title.is_blank() {
+                    self.error = Some(msg().title_missing.to_string());
+                    return None;
+                }
+                self.sending = true;
+                self.error = None;
+                self.worker.post_topic(
+                    node.name.clone(),
+                    self.title.text().trim().to_string(),
+                    self.body.text(),
+                    self.syntax,
+                );
+                None
+            }
+            Action::TopicPosted(result) => {
+                self.sending = false;
+                match result {
+                    Ok(url) => {
+                        self.title = TextInput::default();
+                        self.body = TextInput::default();
+                        self.previewing = false;
+                        self.focus = Field::Title;
+                        Some(Action::OpenTopic(url))
+                    }
+                    Err(error) => {
+                        self.error = Some(error.message);
+                        None
+                    }
+                }
+            }
+            Action::Tick => {
+                self.tick = self.tick.wrapping_add(1);
+                None
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{i18n::msg, model::Node, theme::Theme};
+
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+use ratatui::{
+    layout::{Alignment, Constraint, Rect},
+    text::Span,
+    widgets::{Block, Borders, Row, Table, TableState},
+    Frame,
+};
+
+/// A fuzzy-filtered table of nodes, shared by the nodes page and the node picker.
+#[derive(Default)]
+pub struct NodeList {
+    nodes: Vec<Node>,
+    // Indices into `nodes` that match the filter, best match first.
+    matches: Vec<usize>,
+    filter: String,
+    pub state: TableState,
+}
+
+impl NodeList {
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Replaces the nodes, busiest first.
+    pub fn set_nodes(&mut self, mut nodes: Vec<Node>) {
+        nodes.sort_by_key(|node| std::cmp::Reverse(node.topics));
+        self.nodes = nodes;
+        self.apply_filter();
+    }
+
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    pub fn push_filter(&mut self, c: char) {
+        self.filter.push(c);
+        self.apply_filter();
+    }
+
+    pub fn pop_filter(&mut self) {
+        self.filter.pop();
+        self.apply_filter();
+    }
+
+    pub fn clear_filter(&mut self) {
+        self.filter.clear();
+        self.apply_filter();
+    }
+
+    fn apply_filter(&mut self) {
+        if self.filter.is_empty() {
+            self.matches = (0..self.nodes.len()).collect();
+        } else {
+            let matcher = SkimMatcherV2::default();
+            let mut scored: Vec<(i64, usize)> = self
+                .nodes
+                .iter()
+                .enumerate()
+                .filter_map(|(index, node)| {
+                    let name = matcher.fuzzy_match(&node.name, &self.filter);
+                    let title = matcher.fuzzy_match(&node.title, &self.filter);
+                    name.max(title).map(|score| (score, index))
+                })
+                .collect();
+            scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+            self.matches = scored.into_iter().map(|(_, index)| index).collect();
+        }
+        self.state.select_first();
+    }
+
+    pub fn selected(&self) -> Option<&Node> {
+        self.state
+            .selected()
+            .and_then(|index| self.matches.get(index))
+            .map(|index| &self.nodes[*index])
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let header_cells = msg()
+            .node_columns
+            .iter()
+            .map(|h| Span::styled(*h, theme.header));
+        let header = Row::new(header_cells).height(1).bottom_margin(1);
+        let rows: Vec<Row> = self
+            .matches
+            .iter()
+            .map(|index| {
+                let node = &self.nodes[*index];
+                Row::new(vec![
+                    Span::styled(node.title.as_str(), theme.text),
+                    Span::styled(node.name.as_str(), theme.accent),
+                    Span::styled(node.topics.to_string(), theme.count),
+                    Span::styled(node.header.as_str(), theme.muted),
+                ])
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Percentage(20),
+                Constraint::Percentage(15),
+                Constraint::Percentage(10),
+                Constraint::Percentage(55),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::default()
+                .title(format!(
+                    " {} ",
+                    (msg().nodes_title)(self.matches.len(), self.nodes.len())
+                ))
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL)
+                .style(theme.border),
+        )
+        .column_spacing(1)
+        .row_highlight_style(theme.selection);
+        frame.render_stateful_widget(table, area, &mut self.state);
+    }
+}
@@ -0,0 +1,224 @@
+use std::rc::Rc;
+
+use crate::{
+    action::Action,
+    api::FetchError,
+    i18n::msg,
+    keymap::{Command, Context, Keymap},
+    theme::Theme,
+    worker::Worker,
+};
+
+use super::{node_list::NodeList, widgets, Page, PageType};
+use crossterm::event::{Event, KeyCode};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+pub struct NodesPage {
+    list: NodeList,
+    filtering: bool,
+    loading: bool,
+    error: Option<FetchError>,
+    tick: usize,
+    keymap: Rc<Keymap>,
+    worker: Worker,
+}
+
+impl NodesPage {
+    pub fn new(worker: Worker, keymap: Rc<Keymap>) -> Self {
+        NodesPage {
+            list: NodeList::default(),
+            filtering: false,
+            loading: false,
+            error: None,
+            tick: 0,
+            keymap,
+            worker,
+        }
+    }
+}
+
+impl Page for NodesPage {
+    fn page_type(&self) -> PageType {
+        PageType::Nodes
+    }
+
+    fn init(&mut self) -> Option<Action> {
+        if self.list.is_empty() && !self.loading {
+            Some(Action::FetchNodes)
+        } else {
+            None
+        }
+    }
+
+    fn context(&self) -> Option<Context> {
+        Some(Context::Nodes)
+    }
+
+    fn captures_input(&self) -> bool {
+        self.filtering
+    }
+
+    fn render(&mut self, frame: &mut Frame, theme: &Theme) {
+        let main_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Filter
+                Constraint::Min(0),    // Table
+                Constraint::Length(3), // Footer
+            ])
+            .margin(1)
+            .split(frame.area());
+
+        let filter_style = if self.filtering {
+            theme.notice
+        } else {
+            theme.border
+        };
+        let filter = Paragraph::new(Line::from(vec![
+            Span::styled(msg().filter_prompt, theme.secondary),
+            Span::styled(self.list.filter(), theme.text),
+            Span::styled(if self.filtering { "█" } else { "" }, filter_style),
+        ]))
+        .block(Block::default().borders(Borders::ALL).style(filter_style));
+        frame.render_widget(filter, main_layout[0]);
+
+        if self.loading {
+            let loading = Paragraph::new(widgets::loading_line(self.tick, theme))
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::ALL).style(theme.border));
+            frame.render_widget(loading, main_layout[1]);
+        } else if let Some(error) = &self.error {
+            let hint = self.keymap.hints(
+                Context::Nodes,
+                &[
+                    (msg().hint.retry, &[Command::Reload]),
+                    (msg().hint.back, &[Command::Back]),
+                ],
+            );
+            frame.render_widget(
+                widgets::error_paragraph(error, &hint, theme),
+                main_layout[1],
+            );
+        } else {
+            self.list.render(frame, main_layout[1], theme);
+        }
+
+        let footer_text = if self.filtering {
+            msg().filter_hint.to_string()
+        } else {
+            self.keymap.hints(
+                Context::Nodes,
+                &[
+                    (msg().hint.quit, &[Command::Quit]),
+                    (msg().hint.help, &[Command::Help]),
+                    (msg().hint.back, &[Command::Back]),
+                    (msg().hint.filter, &[Command::Filter]),
+                    (msg().hint.scroll, &[Command::Up, Command::Down]),
+                    (msg().hint.top, &[Command::Top]),
+                    (msg().hint.bottom, &[Command::Bottom]),
+                    (msg().hint.open, &[Command::Open]),
+                ],
+            )
+        };
+        let footer = Paragraph::new(Line::from(Span::styled(footer_text, theme.footer)))
+            .alignment(Alignment::Left)
+            .block(Block::default().borders(Borders::ALL).style(theme.border));
+        frame.render_widget(footer, main_layout[2]);
+    }
+
+    fn handle_event(&mut self, event: Event) -> Option<Action> {
+        let Event::Key(key) = event else {
+            return None;
+        };
+        if !self.filtering {
+            return None;
+        }
+        match key.code {
+            KeyCode::Enter | KeyCode::Esc => Some(Action::EndFilter),
+            KeyCode::Backspace => Some(Action::FilterBackspace),
+            KeyCode::Up => Some(Action::PreviousRow),
+            KeyCode::Down => Some(Action::NextRow),
+            KeyCode::Char(c) => Some(Action::FilterInput(c)),
+            _ => None,
+        }
+    }
+
+    fn handle_command(&mut self, command: Command) -> Option<Action> {
+        match command {
+            Command::Back => Some(Action::GoBack),
+            Command::Filter => Some(Action::StartFilter),
+            Command::Reload => Some(Action::FetchNodes),
+            Command::Top => Some(Action::Top),
+            Command::Bottom => Some(Action::Bottom),
+            Command::Up => Some(Action::PreviousRow),
+            Command::Down => Some(Action::NextRow),
+            Command::Open => self.list.selected().map(|node| Action::OpenNode {
+                name: node.name.clone(),
+                title: node.title.clone(),
+                header: node.header.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    fn update(&mut self, action: Action) -> Option<Action> {
+        match action {
+            Action::FetchNodes => {
+                self.loading = true;
+                self.error = None;
+                self.worker.fetch_nodes();
+                None
+            }
+            Action::NodesLoaded(result) => {
+                self.loading = false;
+                match result {
+                    Ok(nodes) => self.list.set_nodes(nodes),
+                    Err(error) => self.error = Some(error),
+                }
+                None
+            }
+            Action::StartFilter => {
+                self.filtering = true;
+                None
+            }
+            Action::EndFilter => {
+                self.filtering = false;
+                None
+            }
+            Action::FilterInput(c) => {
+                self.list.push_filter(c);
+                None
+            }
+            Action::FilterBackspace => {
+                self.list.pop_filter();
+                None
+            }
+            Action::Tick => {
+                self.tick = self.tick.wrapping_add(1);
+                None
+            }
+            Action::Top => {
+                self.list.state.select_first();
+                None
+            }
+            Action::Bottom => {
+                self.list.state.select_last();
+                None
+            }
+            Action::PreviousRow => {
+                self.list.state.select_previous();
+                None
+            }
+            Action::NextRow => {
+                self.list.state.select_next();
+                None
+            }
+            _ => None,
+        }
+    }
+}
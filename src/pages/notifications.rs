@@ -0,0 +1,299 @@
+use std::rc::Rc;
+
+use crate::{
+    action::Action,
+    api::FetchError,
+    i18n::msg,
+    keymap::{Command, Context, Keymap},
+    model::Notification,
+    theme::Theme,
+    worker::Worker,
+};
+
+use super::{widgets, Page, PageType};
+use crossterm::event::Event;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+pub struct NotificationsPage {
+    notifications: Vec<Notification>,
+    state: TableState,
+    loading: bool,
+    error: Option<FetchError>,
+    // Older pages are fetched when the cursor reaches the end of the list.
+    next_page: u32,
+    has_more: bool,
+    loading_more: bool,
+    more_error: Option<FetchError>,
+    tick: usize,
+    keymap: Rc<Keymap>,
+    worker: Worker,
+}
+
+impl NotificationsPage {
+    pub fn new(worker: Worker, keymap: Rc<Keymap>) -> Self {
+        NotificationsPage {
+            notifications: vec![],
+            state: TableState::default(),
+            loading: false,
+            error: None,
+            next_page: 2,
+            has_more: true,
+            loading_more: false,
+            more_error: None,
+            tick: 0,
+            keymap,
+            worker,
+        }
+    }
+
+    fn selected(&self) -> Option<&Notification> {
+        self.state
+            .selected()
+            .and_then(|index| self.notifications.get(index))
+    }
+
+    fn fetch_more_at_end(&mut self) {
+        // The selection may run past the end until the next render clamps it.
+        let at_end = self
+            .state
+            .selected()
+            .is_some_and(|index| index.saturating_add(1) >= self.notifications.len());
+        if at_end && self.has_more && !self.loading_more && self.more_error.is_none() {
+            self.loading_more = true;
+            self.worker.fetch_notifications(self.next_page);
+        }
+    }
+}
+
+impl Page for NotificationsPage {
+    fn page_type(&self) -> PageType {
+        PageType::Notifications
+    }
+
+    fn context(&self) -> Option<Context> {
+        Some(Context::Notifications)
+    }
+
+    fn init(&mut self) -> Option<Action> {
+        if self.notifications.is_empty() && !self.loading {
+            Some(Action::FetchNotifications)
+        } else {
+            None
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, theme: &Theme) {
+        let main_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),    // Table
+                Constraint::Length(3), // Footer
+            ])
+            .margin(1)
+            .split(frame.area());
+
+        if self.loading {
+            let loading = Paragraph::new(widgets::loading_line(self.tick, theme))
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::ALL).style(theme.border));
+            frame.render_widget(loading, main_layout[0]);
+        } else if let Some(error) = &self.error {
+            let hint = self.keymap.hints(
+                Context::Notifications,
+                &[
+                    (msg().hint.retry, &[Command::Reload]),
+                    (msg().hint.back, &[Command::Back]),
+                ],
+            );
+            frame.render_widget(
+                widgets::error_paragraph(error, &hint, theme),
+                main_layout[0],
+            );
+        } else {
+            let header_cells = msg()
+                .notification_columns
+                .iter()
+                .map(|h| Span::styled(*h, theme.header));
+            let header = Row::new(header_cells).height(1).bottom_margin(1);
+            let rows: Vec<Row> = self
+                .notifications
+                .iter()
+                .map(|notification| {
+                    let content = notification.content.lines().next().unwrap_or_default();
+                    Row::new(vec![
+                        Span::styled(notification.kind.title(), theme.notice),
+                        Span::styled(notification.member.as_str(), theme.accent),
+                        Span::styled(notification.topic_title.as_str(), theme.text),
+                        Span::styled(content.to_string(), theme.secondary),
+                        Span::styled(notification.time.as_str(), theme.muted),
+                    ])
+                })
+                .collect();
+            let status = if self.loading_more {
+                Span::styled(
+                    format!(
+                        " {} {} ",
+                        widgets::spinner(self.tick),
+                        (msg().loading_page)(self.next_page)
+                    ),
+                    theme.notice,
+                )
+            } else if let Some(error) = &self.more_error {
+                let hint = self.keymap.hints(
+                    Context::Notifications,
+                    &[(msg().hint.retry, &[Command::Reload])],
+                );
+                Span::styled(
+                    format!(" {} ", (msg().load_failed)(&error.message, &hint)),
+                    theme.error,
+                )
+            } else if !self.has_more {
+                Span::styled(format!(" {} ", msg().no_more), theme.muted)
+            } else {
+                "".into()
+            };
+            let table = Table::new(
+                rows,
+                [
+                    Constraint::Length(8),
+                    Constraint::Percentage(15),
+                    Constraint::Percentage(35),
+                    Constraint::Percentage(35),
+                    Constraint::Percentage(15),
+                ],
+            )
+            .header(header)
+            .block(
+                Block::default()
+                    .title(format!(
+                        " {} ",
+                        (msg().notifications_title)(self.notifications.len())
+                    ))
+                    .title_alignment(Alignment::Center)
+                    .title_bottom(Line::from(status))
+                    .borders(Borders::ALL)
+                    .style(theme.border),
+            )
+            .column_spacing(1)
+            .row_highlight_style(theme.selection);
+            frame.render_stateful_widget(table, main_layout[0], &mut self.state);
+        }
+
+        let hints = self.keymap.hints(
+            Context::Notifications,
+            &[
+                (msg().hint.quit, &[Command::Quit]),
+                (msg().hint.help, &[Command::Help]),
+                (msg().hint.back, &[Command::Back]),
+                (msg().hint.reload, &[Command::Reload]),
+                (msg().hint.scroll, &[Command::Up, Command::Down]),
+                (msg().hint.top, &[Command::Top]),
+                (msg().hint.bottom, &[Command::Bottom]),
+                (msg().hint.open, &[Command::Open]),
+                (msg().hint.member, &[Command::Author]),
+                (msg().hint.open_browser, &[Command::OpenBrowser]),
+            ],
+        );
+        let footer = Paragraph::new(Line::from(Span::styled(hints, theme.footer)))
+            .alignment(Alignment::Left)
+            .block(Block::default().borders(Borders::ALL).style(theme.border));
+        frame.render_widget(footer, main_layout[1]);
+    }
+
+    fn handle_event(&mut self, _event: Event) -> Option<Action> {
+        None
+    }
+
+    fn handle_command(&mut self, command: Command) -> Option<Action> {
+        match command {
+            Command::Back => Some(Action::GoBack),
+            Command::Reload => Some(Action::FetchNotifications),
+            Command::Top => Some(Action::Top),
+            Command::Bottom => Some(Action::Bottom),
+            Command::Up => Some(Action::PreviousRow),
+            Command::Down => Some(Action::NextRow),
+            Command::Author => self
+                .selected()
+                .map(|notification| Action::OpenMember(notification.member.clone())),
+            Command::OpenBrowser => self
+                .selected()
+                .map(|notification| Action::OpenBrowser(notification.topic_link.clone())),
+            // The link keeps its `#replyN`, so the topic opens at that floor.
+            Command::Open => self
+                .selected()
+                .filter(|notification| !notification.topic_link.is_empty())
+                .map(|notification| Action::OpenTopic(notification.topic_link.clone())),
+            _ => None,
+        }
+    }
+
+    fn update(&mut self, action: Action) -> Option<Action> {
+        match action {
+            Action::FetchNotifications => {
+                self.loading = true;
+                self.error = None;
+                self.more_error = None;
+                self.worker.fetch_notifications(1);
+                None
+            }
+            Action::NotificationsLoaded(1, result) => {
+                self.loading = false;
+                match result {
+                    Ok(notifications) => {
+                        self.has_more = !notifications.is_empty();
+                        self.notifications = notifications;
+                        self.next_page = 2;
+                        self.state.select_first();
+                        // Reading the list on the site marks everything as read.
+                        return Some(Action::CheckUnread);
+                    }
+                    Err(error) => self.error = Some(error),
+                }
+                None
+            }
+            Action::NotificationsLoaded(page, result) => {
+                if page != self.next_page {
+                    return None;
+                }
+                self.loading_more = false;
+                match result {
+                    Ok(notifications) => {
+                        self.has_more = !notifications.is_empty();
+                        self.notifications.extend(notifications);
+                        self.next_page += 1;
+                    }
+                    Err(error) => self.more_error = Some(error),
+                }
+                None
+            }
+            Action::Tick => {
+                self.tick = self.tick.wrapping_add(1);
+                None
+            }
+            Action::Top => {
+                self.state.select_first();
+                None
+            }
+            Action::Bottom => {
+                self.state.select_last();
+                self.fetch_more_at_end();
+                None
+            }
+            Action::PreviousRow => {
+                self.state.select_previous();
+                None
+            }
+            Action::NextRow => {
+                self.state.select_next();
+                self.fetch_more_at_end();
+                None
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,354 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph},
+    Frame,
+};
+
+use crate::{
+    action::Action,
+    api::topic_id,
+    export::Format,
+    i18n::msg,
+    model::Tab,
+    store::commands::CommandHistory,
+    theme::{Preset, Theme},
+};
+
+use super::input::TextInput;
+
+/// The commands of the `:` line and how each one is written.
+const COMMANDS: &[(&str, &str)] = &[
+    ("tab", "tab <name>"),
+    ("node", "node <name>"),
+    ("open", "open <id|url>"),
+    ("member", "member <name>"),
+    ("theme", "theme <preset>"),
+    ("export", "export <format> [file]"),
+    ("nodes", "nodes"),
+    ("notifications", "notifications"),
+    ("bookmarks", "bookmarks"),
+    ("history", "history"),
+    ("back", "back"),
+    ("forward", "forward"),
+];
+
+/// Turns a command such as `tab tech` or `:open 1111950` into the action it
+/// stands for; a topic id becomes a url on `base_url`.
+pub fn parse(command: &str, base_url: &str) -> Result<Action, String> {
+    let mut words = command.trim().trim_start_matches(':').split_whitespace();
+    let name = words.next().unwrap_or_default();
+    let args: Vec<&str> = words.collect();
+    let usage = COMMANDS
+        .iter()
+        .find(|(command, _)| *command == name)
+        .map(|(_, usage)| *usage)
+        .ok_or_else(|| (msg().command_unknown)(name))?;
+    match (name, args.as_slice()) {
+        ("tab", [tab]) => choose(tab, &Tab::TABS, |tab| tab.name()).map(Action::SelectTab),
+        ("node", [node]) => Ok(Action::FetchNode(node.to_string())),
+        ("open", [topic]) if topic.chars().all(|c| c.is_ascii_digit()) => {
+            Ok(Action::OpenTopic(format!("{}/t/{}", base_url, topic)))
+        }
+        ("open", [topic]) if topic_id(topic).is_some() => Ok(Action::OpenTopic(topic.to_string())),
+        ("open", [topic]) => Err((msg().command_not_topic)(topic)),
+        ("member", [member]) => Ok(Action::OpenMember(member.to_string())),
+        ("theme", [preset]) => {
+            choose(preset, &Preset::PRESETS, |preset| preset.name()).map(Action::SetTheme)
+        }
+        ("export", [format, path @ ..]) if path.len() <= 1 => {
+            choose(format, &Format::FORMATS, |format| format.name()).map(|format| {
+                Action::ExportTopic {
+                    format,
+                    path: path.first().map(|path| path.to_string()),
+                }
+            })
+        }
+        ("nodes", []) => Ok(Action::GoNodes),
+        ("notifications", []) => Ok(Action::GoNotifications),
+        ("bookmarks", []) => Ok(Action::GoBookmarks),
+        ("history", []) => Ok(Action::GoHistory),
+        ("back", []) => Ok(Action::GoBack),
+        ("forward", []) => Ok(Action::GoForward),
+        _ => Err((msg().command_usage)(usage)),
+    }
+}
+
+/// The one of `choices` called `value`, or an error listing them all.
+fn choose<T: Copy>(value: &str, choices: &[T], name: fn(&T) -> &'static str) -> Result<T, String> {
+    choices
+        .iter()
+        .find(|choice| name(choice) == value)
+        .copied()
+        .ok_or_else(|| {
+            let names: Vec<&str> = choices.iter().map(name).collect();
+            (msg().command_invalid)(value, &names.join(", "))
+        })
+}
+
+/// Whole lines the text typed so far can be completed to: command names
+/// first, then the tabs, themes and formats they take.
+fn completions(text: &str) -> Vec<String> {
+    let Some((name, arg)) = text.split_once(' ') else {
+        return COMMANDS
+            .iter()
+            .filter(|(command, _)| command.starts_with(text))
+            .map(|(command, usage)| {
+                if usage.contains(' ') {
+                    format!("{} ", command)
+                } else {
+                    command.to_string()
+                }
+            })
+            .collect();
+    };
+    let choices: Vec<&str> = match name {
+        "tab" => Tab::TABS.iter().map(|tab| tab.name()).collect(),
+        "theme" => Preset::PRESETS.iter().map(|preset| preset.name()).collect(),
+        "export" => Format::FORMATS.iter().map(|format| format.name()).collect(),
+        _ => vec![],
+    };
+    choices
+        .into_iter()
+        .filter(|choice| choice.starts_with(arg))
+        .map(|choice| format!("{} {}", name, choice))
+        .collect()
+}
+
+/// The vim-style command line along the bottom of the screen. It takes every
+/// key while open and remembers the commands run from it.
+pub struct Palette {
+    // The command being typed; None while closed.
+    input: Option<TextInput>,
+    // What was wrong with the last command, shown above the line, or on it
+    // once closed until the next key.
+    error: Option<String>,
+    // What Tab cycles through, and the one on the line.
+    completions: Vec<String>,
+    completion: usize,
+    // Up and Down walk the history entries starting with `draft`.
+    browsing: Option<usize>,
+    draft: String,
+    history: CommandHistory,
+}
+
+impl Palette {
+    pub fn new(history: CommandHistory) -> Self {
+        Self {
+            input: None,
+            error: None,
+            completions: vec![],
+            completion: 0,
+            browsing: None,
+            draft: String::new(),
+            history,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.input.is_some()
+    }
+
+    pub fn open(&mut self) {
+        self.show(TextInput::default(), None);
+    }
+
+    /// Puts `command` back on the line with the reason it did not run.
+    pub fn fail(&mut self, command: &str, error: String) {
+        self.show(TextInput::new(command), Some(error));
+    }
+
+    /// Closes the line after `command` ran; only typed commands are kept in
+    /// the history.
+    pub fn finish(&mut self, command: &str) {
+        if self.input.take().is_none() {
+            return;
+        }
+        self.history.record(command);
+        if let Err(e) = self.history.save() {
+            self.error = Some((msg().command_history_save_failed)(&e.to_string()));
+        }
+    }
+
+    /// Drops an error left behind by a closed line.
+    pub fn dismiss(&mut self) {
+        if self.input.is_none() {
+            self.error = None;
+        }
+    }
+
+    /// Returns the command once Enter is pressed on it.
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<String> {
+        let input = self.input.as_mut()?;
+        match key.code {
+            KeyCode::Enter => {
+                let command = input.text().trim().to_string();
+                if !command.is_empty() {
+                    return Some(command);
+                }
+                self.input = None;
+            }
+            KeyCode::Esc => self.input = None,
+            KeyCode::Backspace if input.text().is_empty() => self.input = None,
+            KeyCode::Tab => self.complete(true),
+            KeyCode::BackTab => self.complete(false),
+            KeyCode::Up => self.browse(true),
+            KeyCode::Down => self.browse(false),
+            _ => {
+                input.handle_key(key);
+                self.error = None;
+                self.completions.clear();
+                self.browsing = None;
+            }
+        }
+        None
+    }
+
+    fn show(&mut self, input: TextInput, error: Option<String>) {
+        self.input = Some(input);
+        self.error = error;
+        self.completions.clear();
+        self.browsing = None;
+    }
+
+    fn complete(&mut self, forward: bool) {
+        let Some(input) = &mut self.input else {
+            return;
+        };
+        if self.completions.is_empty() {
+            self.completions = completions(&input.text());
+            self.completion = if forward {
+                0
+            } else {
+                self.completions.len().saturating_sub(1)
+            };
+        } else if forward {
+            self.completion = (self.completion + 1) % self.completions.len();
+        } else {
+            self.completion =
+                (self.completion + self.completions.len() - 1) % self.completions.len();
+        }
+        let Some(completion) = self.completions.get(self.completion) else {
+            return;
+        };
+        *input = TextInput::new(completion);
+        self.error = None;
+        // A single match is taken at once, so the next Tab completes its argument.
+        if self.completions.len() == 1 {
+            self.completions.clear();
+        }
+    }
+
+    fn browse(&mut self, older: bool) {
+        let Some(input) = &mut self.input else {
+            return;
+        };
+        if self.browsing.is_none() {
+            self.draft = input.text();
+        }
+        let entries = self.history.entries();
+        let matches = |index: &usize| entries[*index].starts_with(&self.draft);
+        let next = match (older, self.browsing) {
+            (true, browsing) => {
+                (browsing.map_or(0, |index| index + 1)..entries.len()).find(matches)
+            }
+            (false, Some(browsing)) => (0..browsing).rev().find(matches),
+            (false, None) => return,
+        };
+        match next {
+            Some(index) => *input = TextInput::new(&entries[index]),
+            // Back past the newest entry is what was typed before.
+            None if !older => *input = TextInput::new(&self.draft),
+            None => return,
+        }
+        self.browsing = next;
+        self.completions.clear();
+    }
+
+    pub fn render(&self, frame: &mut Frame, theme: &Theme) {
+        let area = frame.area();
+        if area.height < 2 {
+            return;
+        }
+        let bottom = Rect::new(area.x, area.bottom() - 1, area.width, 1);
+        let above = Rect::new(area.x, area.bottom() - 2, area.width, 1);
+
+        let Some(input) = &self.input else {
+            if let Some(error) = &self.error {
+                frame.render_widget(Clear, bottom);
+                frame.render_widget(Paragraph::new(error.as_str()).style(theme.error), bottom);
+            }
+            return;
+        };
+        let notice = match &self.error {
+            Some(error) => Some(Line::from(Span::styled(error.as_str(), theme.error))),
+            None if !self.completions.is_empty() => Some(Line::from(
+                self.completions
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(index, completion)| {
+                        let word = completion.rsplit(' ').next().unwrap_or(completion);
+                        let style = if index == self.completion {
+                            theme.selection
+                        } else {
+                            theme.secondary
+                        };
+                        [Span::styled(word, style), Span::raw("  ")]
+                    })
+                    .collect::<Vec<_>>(),
+            )),
+            None => None,
+        };
+        if let Some(notice) = notice {
+            frame.render_widget(Clear, above);
+            frame.render_widget(Paragraph::new(notice), above);
+        }
+
+        let [prompt, line] =
+            Layout::horizontal([Constraint::Length(1), Constraint::Min(0)]).areas(bottom);
+        frame.render_widget(Clear, bottom);
+        frame.render_widget(Paragraph::new(":").style(theme.accent), prompt);
+        input.render(frame, line, Block::default().style(theme.text), true);
+    }
+}
+
+#[test]
+fn test_parse_command() {
+    let base_url = "https://www.v2ex.com";
+    assert_eq!(
+        parse("tab tech", base_url),
+        Ok(Action::SelectTab(Tab::Tech))
+    );
+    assert_eq!(
+        parse(":open 1111950", base_url),
+        Ok(Action::OpenTopic(
+            "https://www.v2ex.com/t/1111950".to_string()
+        ))
+    );
+    assert_eq!(
+        parse("export md  notes.md", base_url),
+        Ok(Action::ExportTopic {
+            format: Format::Markdown,
+            path: Some("notes.md".to_string()),
+        })
+    );
+    assert_eq!(
+        parse("node python", base_url),
+        Ok(Action::FetchNode("python".to_string()))
+    );
+    assert_eq!(
+        parse("theme light", base_url),
+        Ok(Action::SetTheme(Preset::Light))
+    );
+    assert!(parse("theme neon", base_url)
+        .unwrap_err()
+        .contains("solarized"));
+    assert!(parse("open abc", base_url).is_err());
+    assert!(parse("member", base_url).is_err());
+    assert!(parse("quux", base_url).is_err());
+
+    assert_eq!(completions("no"), ["node ", "nodes", "notifications"]);
+    assert_eq!(completions("theme s"), ["theme solarized"]);
+    assert!(completions("member l").is_empty());
+}
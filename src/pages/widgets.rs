@@ -0,0 +1,85 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{api::FetchError, i18n::msg, theme::Theme};
+
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Spinner frame for the given tick, advanced by `Action::Tick`.
+pub fn spinner(tick: usize) -> &'static str {
+    SPINNER_FRAMES[tick % SPINNER_FRAMES.len()]
+}
+
+/// Animated "Loading..." line.
+pub fn loading_line(tick: usize, theme: &Theme) -> Line<'static> {
+    Line::from(vec![Span::styled(
+        format!("{} {}", spinner(tick), msg().loading),
+        theme.notice,
+    )])
+}
+
+/// Error panel listing what failed and how to get out of it.
+pub fn error_paragraph<'a>(error: &'a FetchError, hint: &'a str, theme: &Theme) -> Paragraph<'a> {
+    let mut lines = vec![
+        Line::from(vec![Span::styled(msg().request_failed, theme.error.bold())]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(msg().reason, theme.secondary),
+            Span::styled(error.message.as_str(), theme.error),
+        ]),
+    ];
+    if let Some(status) = error.status {
+        lines.push(Line::from(vec![
+            Span::styled(msg().status_code, theme.secondary),
+            Span::styled(status.to_string(), theme.error),
+        ]));
+    }
+    lines.push(Line::from(vec![
+        Span::styled(msg().address, theme.secondary),
+        Span::styled(error.url.as_str(), theme.link),
+    ]));
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![Span::styled(hint, theme.footer)]));
+
+    Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(Block::default().borders(Borders::ALL).style(theme.error))
+}
+
+/// A box of the given size in the middle of `area`, for popups.
+pub fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let [area] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+    area
+}
+
+/// Asks the reader to confirm `prompt` in a popup over the page.
+pub fn confirm_popup(frame: &mut Frame, prompt: &str, theme: &Theme) {
+    let area = centered(frame.area(), 60, 7);
+    let lines = vec![
+        Line::from(Span::styled(prompt, theme.text)),
+        Line::from(""),
+        Line::from(Span::styled(msg().confirm_hint, theme.footer)),
+    ];
+    let popup = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} ", msg().confirm))
+                .style(theme.notice),
+        );
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
@@ -0,0 +1,203 @@
+use std::mem;
+
+use crate::pages::{Page, PageType};
+
+/// Pages kept to go back to; older ones are forgotten.
+const MAX_DEPTH: usize = 50;
+
+/// A page and what it shows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Route {
+    Home,
+    /// The topic at this url, as it was opened, `#replyN` and all.
+    Detail(String),
+    Nodes,
+    NewTopic,
+    Notifications,
+    /// The member with this name.
+    Member(String),
+    Bookmarks,
+    History,
+}
+
+impl Route {
+    pub fn page_type(&self) -> PageType {
+        match self {
+            Route::Home => PageType::Home,
+            Route::Detail(_) => PageType::Detail,
+            Route::Nodes => PageType::Nodes,
+            Route::NewTopic => PageType::NewTopic,
+            Route::Notifications => PageType::Notifications,
+            Route::Member(_) => PageType::Member,
+            Route::Bookmarks => PageType::Bookmarks,
+            Route::History => PageType::History,
+        }
+    }
+}
+
+/// A visited page. Topics and members get a page of their own, so each keeps
+/// its scroll and selection; the other routes share one page and leave it out.
+struct Entry {
+    route: Route,
+    page: Option<Box<dyn Page>>,
+}
+
+/// Where the reader is and how they got there, like a browser: opening a page
+/// pushes it, going back moves it to the forward list, and opening another
+/// page from there drops that list.
+pub struct Router {
+    // The pages there is only one of. They take background results while away
+    // and come back as they were left.
+    shared: Vec<Box<dyn Page>>,
+    back: Vec<Entry>,
+    current: Entry,
+    forward: Vec<Entry>,
+}
+
+impl Router {
+    /// Starts at home, which must be among the `shared` pages.
+    pub fn new(shared: Vec<Box<dyn Page>>) -> Self {
+        Self {
+            shared,
+            back: vec![],
+            current: Entry {
+                route: Route::Home,
+                page: None,
+            },
+            forward: vec![],
+        }
+    }
+
+    pub fn route(&self) -> &Route {
+        &self.current.route
+    }
+
+    /// The page on screen.
+    pub fn page(&mut self) -> &mut dyn Page {
+        match &mut self.current.page {
+            Some(page) => page.as_mut(),
+            None => Self::shared(&mut self.shared, &self.current.route),
+        }
+    }
+
+    /// The page showing `route`, on screen or not; when a topic was opened
+    /// twice, the visit nearest to the current one.
+    pub fn find(&mut self, route: &Route) -> Option<&mut dyn Page> {
+        let entry = std::iter::once(&mut self.current)
+            .chain(self.back.iter_mut().rev())
+            .chain(self.forward.iter_mut().rev())
+            .find(|entry| entry.route == *route && entry.page.is_some());
+        let page = match entry.and_then(|entry| entry.page.as_mut()) {
+            Some(page) => page,
+            None => self
+                .shared
+                .iter_mut()
+                .find(|page| page.page_type() == route.page_type())?,
+        };
+        Some(page.as_mut())
+    }
+
+    /// Opens `route` over the current page, with `page` as its own page or
+    /// the shared one when None.
+    pub fn push(&mut self, route: Route, page: Option<Box<dyn Page>>) {
+        let previous = mem::replace(&mut self.current, Entry { route, page });
+        self.back.push(previous);
+        if self.back.len() > MAX_DEPTH {
+            self.back.remove(0);
+        }
+        self.forward.clear();
+    }
+
+    /// Opens `route` in place of the current page, which cannot be gone back to.
+    pub fn replace(&mut self, route: Route, page: Option<Box<dyn Page>>) {
+        self.current = Entry { route, page };
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        !self.back.is_empty()
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        !self.forward.is_empty()
+    }
+
+    pub fn back(&mut self) {
+        if let Some(entry) = self.back.pop() {
+            self.forward.push(mem::replace(&mut self.current, entry));
+        }
+    }
+
+    pub fn forward(&mut self) {
+        if let Some(entry) = self.forward.pop() {
+            self.back.push(mem::replace(&mut self.current, entry));
+        }
+    }
+
+    fn shared<'a>(shared: &'a mut [Box<dyn Page>], route: &Route) -> &'a mut dyn Page {
+        let page = shared
+            .iter_mut()
+            .find(|page| page.page_type() == route.page_type())
+            .expect("every shared route has a page");
+        page.as_mut()
+    }
+}
+
+#[test]
+fn test_router() {
+    use crate::{action::Action, theme::Theme};
+
+    /// A page that only remembers the topic it was made for.
+    struct Stub(PageType, &'static str);
+
+    impl Page for Stub {
+        fn page_type(&self) -> PageType {
+            self.0
+        }
+        fn init(&mut self) -> Option<Action> {
+            None
+        }
+        fn render(&mut self, _frame: &mut ratatui::Frame, _theme: &Theme) {}
+        fn handle_event(&mut self, _event: crossterm::event::Event) -> Option<Action> {
+            None
+        }
+        fn update(&mut self, _action: Action) -> Option<Action> {
+            Some(Action::OpenTopic(self.1.to_string()))
+        }
+    }
+    let topic = |name: &'static str| Some(Box::new(Stub(PageType::Detail, name)) as Box<dyn Page>);
+    let shown = |page: &mut dyn Page| page.update(Action::Tick);
+
+    let mut router = Router::new(vec![Box::new(Stub(PageType::Home, "home"))]);
+    router.push(Route::Detail("a".to_string()), topic("a"));
+    router.push(
+        Route::Member("livid".to_string()),
+        Some(Box::new(Stub(PageType::Member, "livid"))),
+    );
+    router.push(Route::Detail("b".to_string()), topic("b"));
+    router.back();
+    router.back();
+    assert_eq!(router.route(), &Route::Detail("a".to_string()));
+    assert_eq!(
+        shown(router.page()),
+        Some(Action::OpenTopic("a".to_string()))
+    );
+    assert!(router.find(&Route::Detail("b".to_string())).is_some());
+
+    router.forward();
+    assert_eq!(router.route(), &Route::Member("livid".to_string()));
+    router.push(Route::Home, None);
+    assert!(!router.can_go_forward());
+    assert!(router.find(&Route::Detail("b".to_string())).is_none());
+    assert_eq!(
+        shown(router.page()),
+        Some(Action::OpenTopic("home".to_string()))
+    );
+
+    router.replace(Route::Detail("c".to_string()), topic("c"));
+    router.back();
+    assert_eq!(router.route(), &Route::Member("livid".to_string()));
+    router.back();
+    router.back();
+    assert_eq!(router.route(), &Route::Home);
+    assert!(!router.can_go_back());
+}
@@ -0,0 +1,141 @@
+use std::{io, path::PathBuf};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::{config::data_dir, model::TopicDetail};
+
+/// A topic saved for later, with the copy of it taken when it was saved or
+/// last refreshed. Unrelated to the favorites kept by the account on the site.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub url: String,
+    pub tags: Vec<String>,
+    /// When it was bookmarked, in seconds since the epoch.
+    pub added: i64,
+    pub snapshot: TopicDetail,
+}
+
+/// The bookmarks, newest first, saved to `bookmarks.json` in the data dir.
+pub struct BookmarkStore {
+    path: PathBuf,
+    bookmarks: Vec<Bookmark>,
+}
+
+impl BookmarkStore {
+    pub fn load() -> Self {
+        let path = data_dir().join("bookmarks.json");
+        Self {
+            bookmarks: super::load(&path),
+            path,
+        }
+    }
+
+    pub fn bookmarks(&self) -> &[Bookmark] {
+        &self.bookmarks
+    }
+
+    /// Bookmarks `url`, or refreshes the copy of an existing bookmark while
+    /// keeping its tags and place in the list.
+    pub fn put(&mut self, url: &str, snapshot: TopicDetail) {
+        // The same topic may come with a `#replyN` or `?p=` attached.
+        let url = url.split(['#', '?']).next().unwrap_or(url);
+        match self
+            .bookmarks
+            .iter_mut()
+            .find(|bookmark| bookmark.url == url)
+        {
+            Some(bookmark) => bookmark.snapshot = snapshot,
+            None => self.bookmarks.insert(
+                0,
+                Bookmark {
+                    url: url.to_string(),
+                    tags: vec![],
+                    added: Utc::now().timestamp(),
+                    snapshot,
+                },
+            ),
+        }
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<Bookmark> {
+        (index < self.bookmarks.len()).then(|| self.bookmarks.remove(index))
+    }
+
+    /// Replaces the tags with the words of `tags`, split on spaces or commas.
+    pub fn set_tags(&mut self, index: usize, tags: &str) {
+        if let Some(bookmark) = self.bookmarks.get_mut(index) {
+            bookmark.tags = tags
+                .split(|c: char| c.is_whitespace() || c == ',' || c == '，')
+                .filter(|tag| !tag.is_empty())
+                .map(str::to_string)
+                .fold(vec![], |mut tags, tag| {
+                    if !tags.contains(&tag) {
+                        tags.push(tag);
+                    }
+                    tags
+                });
+        }
+    }
+
+    /// Every tag in use, in the order they first appear.
+    pub fn tags(&self) -> Vec<&str> {
+        let mut tags: Vec<&str> = vec![];
+        for tag in self.bookmarks.iter().flat_map(|bookmark| &bookmark.tags) {
+            if !tags.contains(&tag.as_str()) {
+                tags.push(tag);
+            }
+        }
+        tags
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        super::save(&self.path, &self.bookmarks)
+    }
+}
+
+#[test]
+fn test_bookmark_store() {
+    let path = std::env::temp_dir().join(format!("lazyv2ex-bookmarks-{}.json", std::process::id()));
+    let mut store = BookmarkStore {
+        path: path.clone(),
+        bookmarks: vec![],
+    };
+    let snapshot = TopicDetail {
+        title: "Hello".to_string(),
+        content: String::new(),
+        author: "livid".to_string(),
+        comment: "0".to_string(),
+        updated: String::new(),
+        link: "https://www.v2ex.com/t/42".to_string(),
+        replies: vec![],
+        page_count: 1,
+        favorites: 0,
+        favorited: false,
+        thanked: false,
+    };
+    store.put("https://www.v2ex.com/t/42#reply3", snapshot.clone());
+    store.set_tags(0, "rust, 稍后读 rust");
+    store.put(
+        "https://www.v2ex.com/t/42",
+        TopicDetail {
+            comment: "5".to_string(),
+            ..snapshot.clone()
+        },
+    );
+    store.put("https://www.v2ex.com/t/7", snapshot);
+    store.save().unwrap();
+
+    let store = BookmarkStore {
+        bookmarks: super::load(&path),
+        path,
+    };
+    std::fs::remove_file(&store.path).unwrap();
+    let bookmarks = store.bookmarks();
+    assert_eq!(bookmarks.len(), 2);
+    assert_eq!(bookmarks[0].url, "https://www.v2ex.com/t/7");
+    assert_eq!(bookmarks[1].url, "https://www.v2ex.com/t/42");
+    assert_eq!(bookmarks[1].tags, ["rust", "稍后读"]);
+    assert_eq!(bookmarks[1].snapshot.comment, "5");
+    assert_eq!(store.tags(), ["rust", "稍后读"]);
+}
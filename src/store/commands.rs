@@ -0,0 +1,58 @@
+use std::{io, path::PathBuf};
+
+use crate::config::data_dir;
+
+/// Commands kept in the history; older ones are forgotten.
+const MAX_ENTRIES: usize = 100;
+
+/// Every command run from the `:` line, most recent first and without
+/// repeats, saved to `commands.json` in the data dir.
+pub struct CommandHistory {
+    path: PathBuf,
+    entries: Vec<String>,
+}
+
+impl CommandHistory {
+    pub fn load() -> Self {
+        let path = data_dir().join("commands.json");
+        Self {
+            entries: super::load(&path),
+            path,
+        }
+    }
+
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Moves the command to the top.
+    pub fn record(&mut self, command: &str) {
+        self.entries.retain(|entry| entry != command);
+        self.entries.insert(0, command.to_string());
+        self.entries.truncate(MAX_ENTRIES);
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        super::save(&self.path, &self.entries)
+    }
+}
+
+#[test]
+fn test_command_history() {
+    let path = std::env::temp_dir().join(format!("lazyv2ex-commands-{}.json", std::process::id()));
+    let mut history = CommandHistory {
+        path: path.clone(),
+        entries: vec![],
+    };
+    history.record("tab hot");
+    history.record("open 1");
+    history.record("tab hot");
+    history.save().unwrap();
+
+    let history = CommandHistory {
+        entries: super::load(&path),
+        path,
+    };
+    std::fs::remove_file(&history.path).unwrap();
+    assert_eq!(history.entries(), ["tab hot", "open 1"]);
+}
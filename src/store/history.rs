@@ -0,0 +1,94 @@
+use std::{io, path::PathBuf};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::config::data_dir;
+
+/// Topics kept in the history; older visits are forgotten.
+const MAX_ENTRIES: usize = 500;
+
+/// The last visit to a topic and where the reader left it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub url: String,
+    pub title: String,
+    /// When the topic was last left, in seconds since the epoch.
+    pub visited: i64,
+    /// The detail page's scroll offset at that moment.
+    pub scroll: u16,
+}
+
+/// Every topic opened in the detail page, most recent first, one entry per
+/// topic, saved to `history.json` in the data dir.
+pub struct HistoryStore {
+    path: PathBuf,
+    entries: Vec<HistoryEntry>,
+}
+
+impl HistoryStore {
+    pub fn load() -> Self {
+        let path = data_dir().join("history.json");
+        Self {
+            entries: super::load(&path),
+            path,
+        }
+    }
+
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    /// Moves the topic to the top with the new visit.
+    pub fn record(&mut self, url: &str, title: &str, scroll: u16) {
+        let url = url.split('#').next().unwrap_or(url);
+        self.entries.retain(|entry| entry.url != url);
+        self.entries.insert(
+            0,
+            HistoryEntry {
+                url: url.to_string(),
+                title: title.to_string(),
+                visited: Utc::now().timestamp(),
+                scroll,
+            },
+        );
+        self.entries.truncate(MAX_ENTRIES);
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<HistoryEntry> {
+        (index < self.entries.len()).then(|| self.entries.remove(index))
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        super::save(&self.path, &self.entries)
+    }
+}
+
+#[test]
+fn test_history_store() {
+    let path = std::env::temp_dir().join(format!("lazyv2ex-history-{}.json", std::process::id()));
+    let mut store = HistoryStore {
+        path: path.clone(),
+        entries: vec![],
+    };
+    store.record("https://www.v2ex.com/t/1", "One", 0);
+    store.record("https://www.v2ex.com/t/2", "Two", 5);
+    store.record("https://www.v2ex.com/t/1#reply3", "One", 12);
+    store.save().unwrap();
+
+    let store = HistoryStore {
+        entries: super::load(&path),
+        path,
+    };
+    std::fs::remove_file(&store.path).unwrap();
+    let urls: Vec<&str> = store.entries().iter().map(|e| e.url.as_str()).collect();
+    assert_eq!(
+        urls,
+        ["https://www.v2ex.com/t/1", "https://www.v2ex.com/t/2"]
+    );
+    assert_eq!(store.entries()[0].scroll, 12);
+}
@@ -0,0 +1,27 @@
+use std::{fs, io, path::Path};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+pub mod bookmarks;
+pub mod commands;
+pub mod history;
+pub mod read;
+
+/// Reads a JSON file kept under the data dir. A missing or unreadable file
+/// starts the store over rather than keeping the app from starting.
+fn load<T: DeserializeOwned + Default>(path: &Path) -> T {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Writes the file through a temporary sibling, so a crash never leaves half of it.
+fn save<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, serde_json::to_vec(value)?)?;
+    fs::rename(&tmp, path)
+}
@@ -0,0 +1,96 @@
+use std::{collections::HashMap, io, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{api::topic_id, config::data_dir};
+
+/// How far a topic had got when we last read it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadMark {
+    /// The topic's reply count at the time.
+    pub replies: u32,
+    /// The highest floor loaded in the detail page, where reading picks up.
+    pub floor: u32,
+}
+
+impl ReadMark {
+    /// Replies added since, given the topic's current count. Replies that were
+    /// there but not loaded do not count.
+    pub fn new_replies(&self, replies: u32) -> u32 {
+        replies.saturating_sub(self.replies)
+    }
+}
+
+/// Every topic opened so far, by topic id, saved to `read.json` in the data dir.
+pub struct ReadStore {
+    path: PathBuf,
+    marks: HashMap<u64, ReadMark>,
+}
+
+impl ReadStore {
+    pub fn load() -> Self {
+        let path = data_dir().join("read.json");
+        Self {
+            marks: super::load(&path),
+            path,
+        }
+    }
+
+    pub fn get(&self, url: &str) -> Option<ReadMark> {
+        self.marks.get(&topic_id(url)?).copied()
+    }
+
+    /// Records what the detail page has shown of the topic, never going back on
+    /// what was seen before. Returns whether the mark changed.
+    pub fn mark(&mut self, url: &str, replies: u32, floor: u32) -> bool {
+        let Some(id) = topic_id(url) else {
+            return false;
+        };
+        let mark = self.marks.entry(id).or_default();
+        let updated = ReadMark {
+            replies: mark.replies.max(replies),
+            floor: mark.floor.max(floor),
+        };
+        let changed = *mark != updated;
+        *mark = updated;
+        changed
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        super::save(&self.path, &self.marks)
+    }
+}
+
+#[test]
+fn test_read_store() {
+    let path = std::env::temp_dir().join(format!("lazyv2ex-read-{}.json", std::process::id()));
+    let mut store = ReadStore {
+        path: path.clone(),
+        marks: HashMap::new(),
+    };
+    let url = "https://www.v2ex.com/t/42#reply3";
+    assert_eq!(store.get(url), None);
+    assert!(store.mark(url, 10, 10));
+    assert!(!store.mark("https://www.v2ex.com/t/42", 8, 8));
+    store.save().unwrap();
+
+    let store = ReadStore {
+        marks: super::load(&path),
+        path,
+    };
+    std::fs::remove_file(&store.path).unwrap();
+    let mark = store.get("https://www.v2ex.com/t/42").unwrap();
+    assert_eq!(
+        mark,
+        ReadMark {
+            replies: 10,
+            floor: 10
+        }
+    );
+    assert_eq!(mark.new_replies(13), 3);
+    let skimmed = ReadMark {
+        replies: 300,
+        floor: 100,
+    };
+    assert_eq!(skimmed.new_replies(300), 0);
+}
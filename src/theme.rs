@@ -0,0 +1,421 @@
+use std::{env, str::FromStr};
+
+use ratatui::style::{palette::tailwind, Color, Modifier, Style};
+use serde::Deserialize;
+
+/// Every style the pages draw with, by what it is for rather than its color.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(try_from = "ThemeConfig")]
+pub struct Theme {
+    /// Borders of panes and the text drawn on them.
+    pub border: Style,
+    /// Names that head a view: the site, a topic's title, a member's name.
+    pub title: Style,
+    /// Column headers and markdown headings.
+    pub header: Style,
+    /// The main text of a row, such as a topic title.
+    pub text: Style,
+    /// Labels and quoted or summarized content.
+    pub secondary: Style,
+    /// Times, read topics and other details that should step back.
+    pub muted: Style,
+    /// Authors, tags, node names and code.
+    pub accent: Style,
+    /// Reply and topic counts.
+    pub count: Style,
+    /// Spinners, notices and anything new since the last visit.
+    pub notice: Style,
+    /// The selected row of a list.
+    pub selection: Style,
+    pub tab: Style,
+    pub tab_selected: Style,
+    /// Reply headers and bodies in a topic.
+    pub reply: Style,
+    /// The header of a reply written by the topic's author.
+    pub op: Style,
+    pub link: Style,
+    pub footer: Style,
+    pub error: Style,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Preset {
+    #[default]
+    Dark,
+    Light,
+    Solarized,
+    /// The Okabe-Ito palette, which keeps its colors apart for every kind of
+    /// color vision; errors are bold so they never rely on hue alone.
+    Colorblind,
+}
+
+impl Preset {
+    pub const PRESETS: [Preset; 4] = [
+        Preset::Dark,
+        Preset::Light,
+        Preset::Solarized,
+        Preset::Colorblind,
+    ];
+
+    /// The name the config and the `:theme` command use.
+    pub fn name(self) -> &'static str {
+        match self {
+            Preset::Dark => "dark",
+            Preset::Light => "light",
+            Preset::Solarized => "solarized",
+            Preset::Colorblind => "colorblind",
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::preset(Preset::default())
+    }
+}
+
+const fn fg(color: Color) -> Style {
+    Style::new().fg(color)
+}
+
+const fn bold(color: Color) -> Style {
+    Style::new().fg(color).add_modifier(Modifier::BOLD)
+}
+
+const fn reversed(color: Color) -> Style {
+    Style::new().fg(color).add_modifier(Modifier::REVERSED)
+}
+
+const fn underlined(color: Color) -> Style {
+    Style::new().fg(color).add_modifier(Modifier::UNDERLINED)
+}
+
+const fn tab_selected(color: Color) -> Style {
+    Style::new()
+        .fg(color)
+        .add_modifier(Modifier::BOLD.union(Modifier::REVERSED))
+}
+
+impl Theme {
+    pub fn preset(preset: Preset) -> Self {
+        match preset {
+            Preset::Dark => Self {
+                border: fg(Color::Cyan),
+                title: bold(Color::Cyan),
+                header: bold(Color::Yellow),
+                text: fg(Color::White),
+                secondary: fg(Color::Gray),
+                muted: fg(Color::DarkGray),
+                accent: fg(Color::Green),
+                count: fg(Color::Cyan),
+                notice: fg(Color::Yellow),
+                selection: reversed(tailwind::BLUE.c400),
+                tab: fg(Color::Gray),
+                tab_selected: tab_selected(Color::Cyan),
+                reply: bold(Color::Cyan),
+                op: bold(Color::Magenta),
+                link: underlined(Color::Blue),
+                footer: bold(Color::Cyan),
+                error: fg(Color::Red),
+            },
+            Preset::Light => Self {
+                border: fg(tailwind::SKY.c700),
+                title: bold(tailwind::SKY.c800),
+                header: bold(tailwind::AMBER.c700),
+                text: fg(tailwind::SLATE.c900),
+                secondary: fg(tailwind::SLATE.c600),
+                muted: fg(tailwind::SLATE.c400),
+                accent: fg(tailwind::GREEN.c700),
+                count: fg(tailwind::SKY.c700),
+                notice: fg(tailwind::ORANGE.c600),
+                selection: reversed(tailwind::BLUE.c600),
+                tab: fg(tailwind::SLATE.c500),
+                tab_selected: tab_selected(tailwind::SKY.c700),
+                reply: fg(tailwind::SLATE.c800),
+                op: bold(tailwind::FUCHSIA.c700),
+                link: underlined(tailwind::BLUE.c700),
+                footer: bold(tailwind::SKY.c700),
+                error: fg(tailwind::RED.c600),
+            },
+            Preset::Solarized => {
+                const BASE01: Color = Color::Rgb(0x58, 0x6e, 0x75);
+                const BASE0: Color = Color::Rgb(0x83, 0x94, 0x96);
+                const BASE1: Color = Color::Rgb(0x93, 0xa1, 0xa1);
+                const YELLOW: Color = Color::Rgb(0xb5, 0x89, 0x00);
+                const ORANGE: Color = Color::Rgb(0xcb, 0x4b, 0x16);
+                const RED: Color = Color::Rgb(0xdc, 0x32, 0x2f);
+                const MAGENTA: Color = Color::Rgb(0xd3, 0x36, 0x82);
+                const VIOLET: Color = Color::Rgb(0x6c, 0x71, 0xc4);
+                const BLUE: Color = Color::Rgb(0x26, 0x8b, 0xd2);
+                const CYAN: Color = Color::Rgb(0x2a, 0xa1, 0x98);
+                const GREEN: Color = Color::Rgb(0x85, 0x99, 0x00);
+                Self {
+                    border: fg(CYAN),
+                    title: bold(BLUE),
+                    header: bold(YELLOW),
+                    text: fg(BASE1),
+                    secondary: fg(BASE0),
+                    muted: fg(BASE01),
+                    accent: fg(GREEN),
+                    count: fg(CYAN),
+                    notice: fg(ORANGE),
+                    selection: reversed(BLUE),
+                    tab: fg(BASE01),
+                    tab_selected: tab_selected(BLUE),
+                    reply: fg(BASE0),
+                    op: bold(MAGENTA),
+                    link: underlined(VIOLET),
+                    footer: bold(CYAN),
+                    error: fg(RED),
+                }
+            }
+            Preset::Colorblind => {
+                const ORANGE: Color = Color::Rgb(0xe6, 0x9f, 0x00);
+                const SKY_BLUE: Color = Color::Rgb(0x56, 0xb4, 0xe9);
+                const BLUISH_GREEN: Color = Color::Rgb(0x00, 0x9e, 0x73);
+                const YELLOW: Color = Color::Rgb(0xf0, 0xe4, 0x42);
+                const VERMILLION: Color = Color::Rgb(0xd5, 0x5e, 0x00);
+                const REDDISH_PURPLE: Color = Color::Rgb(0xcc, 0x79, 0xa7);
+                Self {
+                    border: fg(SKY_BLUE),
+                    title: bold(SKY_BLUE),
+                    header: bold(YELLOW),
+                    text: fg(Color::White),
+                    secondary: fg(Color::Gray),
+                    muted: fg(Color::DarkGray),
+                    accent: fg(BLUISH_GREEN),
+                    count: fg(SKY_BLUE),
+                    notice: fg(ORANGE),
+                    selection: reversed(SKY_BLUE),
+                    tab: fg(Color::Gray),
+                    tab_selected: tab_selected(SKY_BLUE),
+                    reply: fg(Color::White),
+                    op: bold(ORANGE),
+                    link: underlined(REDDISH_PURPLE),
+                    footer: bold(SKY_BLUE),
+                    error: bold(VERMILLION),
+                }
+            }
+        }
+    }
+
+    fn styles_mut(&mut self) -> [&mut Style; 17] {
+        [
+            &mut self.border,
+            &mut self.title,
+            &mut self.header,
+            &mut self.text,
+            &mut self.secondary,
+            &mut self.muted,
+            &mut self.accent,
+            &mut self.count,
+            &mut self.notice,
+            &mut self.selection,
+            &mut self.tab,
+            &mut self.tab_selected,
+            &mut self.reply,
+            &mut self.op,
+            &mut self.link,
+            &mut self.footer,
+            &mut self.error,
+        ]
+    }
+
+    /// Drops the colors the terminal cannot show. Without any colors the
+    /// selections still stand out, in reverse video.
+    pub fn for_terminal(mut self, colors: ColorSupport) -> Self {
+        let keep = |color: Option<Color>| match (colors, color) {
+            (ColorSupport::Full, color) => color,
+            (ColorSupport::Basic, Some(Color::Rgb(..) | Color::Indexed(_))) => None,
+            (ColorSupport::Basic, color) => color,
+            (ColorSupport::None, _) => None,
+        };
+        for style in self.styles_mut() {
+            style.fg = keep(style.fg);
+            style.bg = keep(style.bg);
+        }
+        if colors != ColorSupport::Full {
+            self.selection = self.selection.add_modifier(Modifier::REVERSED);
+            self.tab_selected = self.tab_selected.add_modifier(Modifier::REVERSED);
+        }
+        self
+    }
+}
+
+/// How many colors the terminal can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSupport {
+    None,
+    /// The 16 ANSI colors only.
+    Basic,
+    Full,
+}
+
+impl ColorSupport {
+    /// Honors `NO_COLOR` (https://no-color.org), then guesses from
+    /// `COLORTERM` and `TERM`.
+    pub fn detect() -> Self {
+        let var = |name| env::var(name).unwrap_or_default();
+        if !var("NO_COLOR").is_empty() {
+            return ColorSupport::None;
+        }
+        if matches!(var("COLORTERM").as_str(), "truecolor" | "24bit") {
+            return ColorSupport::Full;
+        }
+        let term = var("TERM");
+        match term.as_str() {
+            // Windows consoles leave TERM unset and show every color.
+            "" => ColorSupport::Full,
+            "dumb" => ColorSupport::None,
+            _ if ["256color", "truecolor", "direct"]
+                .iter()
+                .any(|suffix| term.contains(suffix)) =>
+            {
+                ColorSupport::Full
+            }
+            _ => ColorSupport::Basic,
+        }
+    }
+}
+
+/// A style written like `yellow bold`, `#ff8800` or `black on yellow`: at most
+/// one foreground color, `on` and a background color, and any modifiers.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(try_from = "String")]
+struct StyleSpec(Style);
+
+impl FromStr for StyleSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut style = Style::new();
+        let mut words = s.split_whitespace();
+        while let Some(word) = words.next() {
+            let modifier = match word.to_ascii_lowercase().as_str() {
+                "bold" => Modifier::BOLD,
+                "dim" => Modifier::DIM,
+                "italic" => Modifier::ITALIC,
+                "underlined" => Modifier::UNDERLINED,
+                "reversed" => Modifier::REVERSED,
+                "crossed_out" => Modifier::CROSSED_OUT,
+                "on" => {
+                    let color = words
+                        .next()
+                        .ok_or_else(|| format!("missing a color after `on` in `{}`", s))?;
+                    style.bg = Some(parse_color(color)?);
+                    continue;
+                }
+                _ if style.fg.is_none() => {
+                    style.fg = Some(parse_color(word)?);
+                    continue;
+                }
+                _ => return Err(format!("unknown style `{}` in `{}`", word, s)),
+            };
+            style = style.add_modifier(modifier);
+        }
+        Ok(Self(style))
+    }
+}
+
+impl TryFrom<String> for StyleSpec {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+fn parse_color(value: &str) -> Result<Color, String> {
+    value
+        .parse()
+        .map_err(|_| format!("`{}` is not a color", value))
+}
+
+/// The `[theme]` section: a preset, and any of its styles changed. A changed
+/// style takes the given colors and adds the given modifiers.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ThemeConfig {
+    preset: Preset,
+    border: Option<StyleSpec>,
+    title: Option<StyleSpec>,
+    header: Option<StyleSpec>,
+    text: Option<StyleSpec>,
+    secondary: Option<StyleSpec>,
+    muted: Option<StyleSpec>,
+    accent: Option<StyleSpec>,
+    count: Option<StyleSpec>,
+    notice: Option<StyleSpec>,
+    selection: Option<StyleSpec>,
+    tab: Option<StyleSpec>,
+    tab_selected: Option<StyleSpec>,
+    reply: Option<StyleSpec>,
+    op: Option<StyleSpec>,
+    link: Option<StyleSpec>,
+    footer: Option<StyleSpec>,
+    error: Option<StyleSpec>,
+}
+
+impl TryFrom<ThemeConfig> for Theme {
+    type Error = String;
+
+    fn try_from(config: ThemeConfig) -> Result<Self, Self::Error> {
+        let mut theme = Theme::preset(config.preset);
+        let specs = [
+            config.border,
+            config.title,
+            config.header,
+            config.text,
+            config.secondary,
+            config.muted,
+            config.accent,
+            config.count,
+            config.notice,
+            config.selection,
+            config.tab,
+            config.tab_selected,
+            config.reply,
+            config.op,
+            config.link,
+            config.footer,
+            config.error,
+        ];
+        for (style, spec) in theme.styles_mut().into_iter().zip(specs) {
+            if let Some(StyleSpec(spec)) = spec {
+                *style = style.patch(spec);
+            }
+        }
+        Ok(theme)
+    }
+}
+
+#[test]
+fn test_theme() {
+    let spec: StyleSpec = "#ff8800 on black bold".parse().unwrap();
+    assert_eq!(
+        spec.0,
+        Style::new()
+            .fg(Color::Rgb(0xff, 0x88, 0x00))
+            .bg(Color::Black)
+            .add_modifier(Modifier::BOLD)
+    );
+    assert!("red blue".parse::<StyleSpec>().is_err());
+    assert!("on".parse::<StyleSpec>().is_err());
+
+    let theme = Theme::preset(Preset::Dark);
+    assert_eq!(theme.clone().for_terminal(ColorSupport::Full), theme);
+    let basic = theme.clone().for_terminal(ColorSupport::Basic);
+    assert_eq!(basic.header, theme.header);
+    assert_eq!(
+        basic.selection,
+        Style::new().add_modifier(Modifier::REVERSED)
+    );
+
+    let mono = Theme::preset(Preset::Solarized).for_terminal(ColorSupport::None);
+    assert_eq!(mono.title, Style::new().add_modifier(Modifier::BOLD));
+    assert_eq!(
+        mono.tab_selected,
+        Style::new().add_modifier(Modifier::BOLD | Modifier::REVERSED)
+    );
+}
@@ -2,26 +2,41 @@ use chrono::{DateTime, Local, Utc};
 
 pub mod time_formatting {
     use super::*;
+    use crate::i18n::{msg, Messages};
 
     pub fn format_relative_time(time: DateTime<Utc>) -> String {
         let now = Local::now();
         let local_time = time.with_timezone(&now.timezone());
-        let duration = now.signed_duration_since(local_time);
+        describe(now.signed_duration_since(local_time), msg())
+    }
 
+    fn describe(duration: chrono::TimeDelta, messages: &Messages) -> String {
         if duration.num_days() > 0 {
-            format!("{}天前", duration.num_days())
+            (messages.days_ago)(duration.num_days())
         } else if duration.num_hours() > 0 {
             let hours = duration.num_hours();
             let minutes = duration.num_minutes() % 60;
             if minutes > 0 {
-                format!("{}小时{}分钟前", hours, minutes)
+                (messages.hours_minutes_ago)(hours, minutes)
             } else {
-                format!("{}小时前", hours)
+                (messages.hours_ago)(hours)
             }
         } else if duration.num_minutes() > 0 {
-            format!("{}分钟前", duration.num_minutes())
+            (messages.minutes_ago)(duration.num_minutes())
         } else {
-            format!("{}秒前", duration.num_seconds())
+            (messages.seconds_ago)(duration.num_seconds())
         }
     }
+
+    #[test]
+    fn test_describe() {
+        use crate::i18n::{EN, ZH_CN};
+        use chrono::TimeDelta;
+
+        assert_eq!(describe(TimeDelta::days(3), &ZH_CN), "3天前");
+        assert_eq!(describe(TimeDelta::days(1), &EN), "1 day ago");
+        assert_eq!(describe(TimeDelta::minutes(61), &EN), "1 hour 1 minute ago");
+        assert_eq!(describe(TimeDelta::hours(2), &EN), "2 hours ago");
+        assert_eq!(describe(TimeDelta::seconds(0), &EN), "0 seconds ago");
+    }
 }
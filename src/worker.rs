@@ -0,0 +1,172 @@
+use std::{sync::mpsc::Sender, thread, time::Duration};
+
+use crate::{
+    action::Action,
+    api::{v2::RateLimit, Crawler},
+    checkin::{self, CheckInRecord},
+    model::{Feed, Syntax},
+};
+
+/// Runs network requests off the UI thread and reports the results back as actions.
+#[derive(Clone)]
+pub struct Worker {
+    crawler: Crawler,
+    sender: Sender<Action>,
+}
+
+impl Worker {
+    pub fn new(crawler: Crawler, sender: Sender<Action>) -> Self {
+        Self { crawler, sender }
+    }
+
+    pub fn has_token(&self) -> bool {
+        self.crawler.has_token()
+    }
+
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        self.crawler.rate_limit()
+    }
+
+    pub fn check_session(&self) {
+        self.spawn(|crawler| Action::SessionChecked(crawler.check_session()));
+    }
+
+    pub fn check_token(&self) {
+        self.spawn(|crawler| Action::TokenChecked(crawler.fetch_token_owner()));
+    }
+
+    pub fn check_unread(&self) {
+        self.spawn(|crawler| Action::UnreadLoaded(crawler.fetch_unread_count()));
+    }
+
+    /// Checks the unread notification count every `interval` for as long as the app runs.
+    pub fn poll_unread(&self, interval: Duration) {
+        let crawler = self.crawler.clone();
+        let sender = self.sender.clone();
+        thread::spawn(move || loop {
+            if sender
+                .send(Action::UnreadLoaded(crawler.fetch_unread_count()))
+                .is_err()
+            {
+                break;
+            }
+            thread::sleep(interval);
+        });
+    }
+
+    pub fn fetch_notifications(&self, page: u32) {
+        self.spawn(move |crawler| {
+            Action::NotificationsLoaded(page, crawler.fetch_notifications(page))
+        });
+    }
+
+    pub fn check_in(&self) {
+        self.spawn(|crawler| Action::CheckedIn(checkin::run(crawler, &CheckInRecord::load())));
+    }
+
+    pub fn fetch_member(&self, username: String) {
+        self.spawn(move |crawler| {
+            let result = crawler.fetch_member(&username);
+            Action::MemberLoaded(username, result)
+        });
+    }
+
+    pub fn fetch_topics(&self, feed: Feed) {
+        self.spawn(move |crawler| {
+            let result = match &feed {
+                Feed::Tab(tab) => crawler.fetch_topics(*tab),
+                Feed::Node { name, .. } => crawler.fetch_node_topics(name),
+            };
+            Action::TopicsLoaded(feed, result)
+        });
+    }
+
+    pub fn fetch_topic_replies(&self, url: String, page: u32) {
+        self.spawn(move |crawler| {
+            let result = crawler.fetch_topic_replies(&url, page);
+            Action::RepliesLoaded(url, page, result)
+        });
+    }
+
+    pub fn fetch_nodes(&self) {
+        self.spawn(|crawler| Action::NodesLoaded(crawler.fetch_nodes()));
+    }
+
+    pub fn fetch_node(&self, name: String) {
+        self.spawn(move |crawler| {
+            let result = crawler.fetch_node(&name);
+            Action::NodeLoaded(name, result)
+        });
+    }
+
+    pub fn fetch_more_topics(&self, feed: Feed, page: u32) {
+        self.spawn(move |crawler| {
+            let result = crawler.fetch_topic_list(&feed, page);
+            Action::MoreTopicsLoaded(feed, page, result)
+        });
+    }
+
+    pub fn fetch_topic_detail(&self, url: String) {
+        self.spawn(move |crawler| {
+            let result = crawler.fetch_topic_detail(&url);
+            Action::TopicDetailLoaded(url, result)
+        });
+    }
+
+    pub fn fetch_topic_snapshot(&self, url: String) {
+        self.spawn(move |crawler| {
+            let result = crawler.fetch_topic_snapshot(&url);
+            Action::SnapshotLoaded(url, result)
+        });
+    }
+
+    pub fn post_reply(&self, url: String, content: String) {
+        self.spawn(move |crawler| {
+            let result = crawler.post_reply(&url, &content);
+            Action::ReplyPosted(url, result)
+        });
+    }
+
+    pub fn post_topic(&self, node: String, title: String, content: String, syntax: Syntax) {
+        self.spawn(move |crawler| {
+            Action::TopicPosted(crawler.post_topic(&node, &title, &content, syntax))
+        });
+    }
+
+    /// Thanks the reply with the given id, or the topic itself.
+    pub fn thank(&self, url: String, reply_id: Option<u64>) {
+        self.spawn(move |crawler| {
+            let result = match reply_id {
+                Some(id) => crawler.thank_reply(&url, id),
+                None => crawler.thank_topic(&url),
+            };
+            Action::ThankSent(url, reply_id, result)
+        });
+    }
+
+    pub fn favorite_topic(&self, url: String, favorite: bool) {
+        self.spawn(move |crawler| {
+            let result = crawler.favorite_topic(&url, favorite);
+            Action::FavoriteSet(url, favorite, result)
+        });
+    }
+
+    pub fn ignore_topic(&self, url: String) {
+        self.spawn(move |crawler| {
+            let result = crawler.ignore_topic(&url);
+            Action::TopicIgnored(url, result)
+        });
+    }
+
+    fn spawn<F>(&self, job: F)
+    where
+        F: FnOnce(&Crawler) -> Action + Send + 'static,
+    {
+        let crawler = self.crawler.clone();
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            // The receiver only goes away when the app is shutting down.
+            let _ = sender.send(job(&crawler));
+        });
+    }
+}